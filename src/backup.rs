@@ -0,0 +1,300 @@
+use std::{
+    cmp::Reverse,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, NaiveDateTime, SubsecRound, Utc};
+
+use crate::{
+    SaveFile,
+    error::SaveError,
+    layout::{SaveSection, slot_coverage},
+    save_data_info::{
+        SAVE_FILE_HEADER_FIELDS, SAVE_SLOT_BASE_ADDRESSES, SAVE_SLOT_SIZE, SYSTEM_AREA_START,
+        SaveDataVar,
+    },
+    save_file_parser::fnv1a_hash,
+};
+
+/// How many backups of each save file are kept when no other count is given.
+pub const DEFAULT_BACKUPS_KEPT: usize = 10;
+
+/// Backups are named after the UTC date they were taken, the local time can go back when the
+/// clocks change. The name ends with a number for the backups taken in the same second.
+const BACKUP_DATE_FORMAT: &str = "%Y-%m-%d_%H-%M-%S";
+const BACKUP_DATE_SUFFIX: &str = "_UTC";
+
+/// A copy of a save file taken before it was overwritten.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backup {
+    pub path: PathBuf,
+    pub date: DateTime<Utc>,
+    /// Starts at 1, goes up for every other backup taken in the same second.
+    pub copy_number: u32,
+    /// The file the backup was taken from and what was in each of its slots.
+    pub summary: Vec<String>,
+}
+
+impl Backup {
+    /// The backed up save, without a path so it can't be written over the backup by mistake.
+    pub fn save_file(&self) -> Result<SaveFile, SaveError> {
+        let data = fs::read(&self.path).map_err(|source| SaveError::Io {
+            path: self.path.clone(),
+            source,
+        })?;
+        SaveFile::from_bytes(data)
+    }
+
+    fn summary_path(&self) -> PathBuf {
+        self.path.with_extension("txt")
+    }
+}
+
+fn backup_file_name(date: &DateTime<Utc>, copy_number: u32) -> String {
+    format!(
+        "{}{}_{:02}.dat",
+        date.format(BACKUP_DATE_FORMAT),
+        BACKUP_DATE_SUFFIX,
+        copy_number
+    )
+}
+
+/// The date and copy number in the name of a backup, without the extension.
+fn parse_backup_name(name: &str) -> Option<(DateTime<Utc>, u32)> {
+    let (date, copy_number) = name.rsplit_once('_')?;
+    let date = date.strip_suffix(BACKUP_DATE_SUFFIX)?;
+    let date = NaiveDateTime::parse_from_str(date, BACKUP_DATE_FORMAT).ok()?;
+    Some((date.and_utc(), copy_number.parse().ok()?))
+}
+
+/// Folder with the backups of the save file at `save_path`. Every save file gets its own folder,
+/// named after the folder it's in (the Steam account for the game's saves) and its whole path.
+pub fn backup_folder(save_path: &Path) -> Result<PathBuf, SaveError> {
+    let data_dir = dirs::data_local_dir().ok_or_else(|| {
+        SaveError::PathNotFound("the folder to keep the backups in could not be found".to_string())
+    })?;
+    let folder_name = save_path
+        .parent()
+        .and_then(|parent| parent.file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "save".to_string());
+    // the hash has to stay the same between builds, so `DefaultHasher` can't be used
+    let path_hash = fnv1a_hash(save_path.to_string_lossy().as_bytes());
+    Ok(data_dir
+        .join("pw2repac-save-editor")
+        .join("backups")
+        .join(format!("{}-{:016x}", folder_name, path_hash)))
+}
+
+/// One line for every slot of the save, with the lives, play time and medals of the ones in use.
+pub fn slot_summaries(save_file: &SaveFile) -> Vec<String> {
+    (1..=4)
+        .map(|slot_number| {
+            let slot = save_file.slot(slot_number);
+            if !slot.exists().unwrap_or(false) {
+                return format!("Save {}: empty", slot_number);
+            }
+            let summary = || -> Result<String, SaveError> {
+                Ok(format!(
+                    "Save {}: {} lives, {:03}:{:02}:{:02}, {} medals",
+                    slot_number,
+                    slot.get_int(SaveDataVar::Lives)?,
+                    slot.get_int(SaveDataVar::PlayTimeHours)?,
+                    slot.get_int(SaveDataVar::PlayTimeMinutes)?,
+                    slot.get_int(SaveDataVar::PlayTimeSeconds)?,
+                    slot.get_int(SaveDataVar::MedalNum)?,
+                ))
+            };
+            summary().unwrap_or_else(|e| format!("Save {}: {}", slot_number, e))
+        })
+        .collect()
+}
+
+/// Copies the file at `save_path` into `folder` before it's overwritten, and deletes the oldest
+/// backups so only `keep` are left. Nothing is copied if the file doesn't exist yet or if `keep`
+/// is 0, which turns the backups off.
+pub fn create_backup(
+    save_path: &Path,
+    folder: &Path,
+    keep: usize,
+) -> Result<Option<Backup>, SaveError> {
+    if keep == 0 || !save_path.is_file() {
+        return Ok(None);
+    }
+    let io_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |source| SaveError::Io { path, source }
+    };
+
+    let data = fs::read(save_path).map_err(io_error(save_path))?;
+    let mut summary = vec![format!("Source: {}", save_path.display())];
+    match SaveFile::from_bytes(data.clone()) {
+        Ok(save_file) => summary.extend(slot_summaries(&save_file)),
+        Err(e) => summary.push(e.to_string()),
+    }
+
+    fs::create_dir_all(folder).map_err(io_error(folder))?;
+    let date = Utc::now().trunc_subsecs(0);
+    let mut copy_number = 1;
+    while folder.join(backup_file_name(&date, copy_number)).exists() {
+        copy_number += 1;
+    }
+
+    let backup = Backup {
+        path: folder.join(backup_file_name(&date, copy_number)),
+        date,
+        copy_number,
+        summary,
+    };
+    fs::write(&backup.path, &data).map_err(io_error(&backup.path))?;
+    let summary_path = backup.summary_path();
+    fs::write(&summary_path, backup.summary.join("\n")).map_err(io_error(&summary_path))?;
+
+    prune_backups(folder, keep)?;
+    Ok(Some(backup))
+}
+
+/// The backups in `folder`, newest first.
+pub fn list_backups(folder: &Path) -> Result<Vec<Backup>, SaveError> {
+    let entries = match fs::read_dir(folder) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(source) => {
+            return Err(SaveError::Io {
+                path: folder.to_path_buf(),
+                source,
+            });
+        }
+    };
+
+    let mut backups: Vec<Backup> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "dat"))
+        .filter_map(|path| {
+            let (date, copy_number) = parse_backup_name(&path.file_stem()?.to_string_lossy())?;
+            let summary = fs::read_to_string(path.with_extension("txt"))
+                .map(|text| text.lines().map(str::to_string).collect())
+                .unwrap_or_default();
+            Some(Backup {
+                path,
+                date,
+                copy_number,
+                summary,
+            })
+        })
+        .collect();
+    backups.sort_by_key(|backup| Reverse((backup.date, backup.copy_number)));
+    Ok(backups)
+}
+
+/// Deletes the oldest backups in `folder` so only `keep` are left.
+pub fn prune_backups(folder: &Path, keep: usize) -> Result<(), SaveError> {
+    for backup in list_backups(folder)?.iter().skip(keep) {
+        fs::remove_file(&backup.path).map_err(|source| SaveError::Io {
+            path: backup.path.clone(),
+            source,
+        })?;
+        // old backups may not have a summary
+        let _ = fs::remove_file(backup.summary_path());
+    }
+    Ok(())
+}
+
+/// What is different in one section between two save files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionDiff {
+    pub section: SaveSection,
+    /// Variables with at least one different byte.
+    pub vars: Vec<SaveDataVar>,
+    /// Different bytes that no variable describes.
+    pub unknown_bytes: usize,
+}
+
+/// The sections that are different between `old` and `new`, which are whole save files.
+pub fn diff_save_files(old: &[u8], new: &[u8]) -> Vec<SectionDiff> {
+    let differs = |start: usize, end: usize| old.get(start..end) != new.get(start..end);
+    let different_bytes =
+        |start: usize, end: usize| (start..end).filter(|i| old.get(*i) != new.get(*i)).count();
+
+    let mut diffs = vec![];
+
+    let header_vars: Vec<SaveDataVar> = SAVE_FILE_HEADER_FIELDS
+        .iter()
+        .filter(|field| {
+            let start = field.offset as usize;
+            differs(start, start + field.int_type.size() as usize)
+        })
+        .map(|field| field.var)
+        .collect();
+    if !header_vars.is_empty() {
+        diffs.push(SectionDiff {
+            section: SaveSection::Header,
+            vars: header_vars,
+            unknown_bytes: 0,
+        });
+    }
+
+    let coverage = slot_coverage();
+    for (slot, base) in (1..).zip(SAVE_SLOT_BASE_ADDRESSES) {
+        let base = base as usize;
+        if !differs(base, base + SAVE_SLOT_SIZE as usize) {
+            continue;
+        }
+        let mut diff = SectionDiff {
+            section: SaveSection::Slot(slot),
+            vars: vec![],
+            unknown_bytes: 0,
+        };
+        for range in &coverage {
+            let (start, end) = (base + range.start as usize, base + range.end as usize);
+            if !differs(start, end) {
+                continue;
+            }
+            if range.is_known() {
+                for var in &range.vars {
+                    if !diff.vars.contains(var) {
+                        diff.vars.push(*var);
+                    }
+                }
+            } else {
+                diff.unknown_bytes += different_bytes(start, end);
+            }
+        }
+        diffs.push(diff);
+    }
+
+    let system_area_start = SYSTEM_AREA_START as usize;
+    let system_area_end = old.len().max(new.len());
+    if differs(system_area_start, system_area_end) {
+        diffs.push(SectionDiff {
+            section: SaveSection::SystemArea,
+            vars: vec![],
+            unknown_bytes: different_bytes(system_area_start, system_area_end),
+        });
+    }
+
+    diffs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backup_names_keep_the_date_and_copy_number() {
+        let date = NaiveDateTime::parse_from_str("2025-10-26_01-30-00", BACKUP_DATE_FORMAT)
+            .unwrap()
+            .and_utc();
+        let name = backup_file_name(&date, 10);
+        assert_eq!(name, "2025-10-26_01-30-00_UTC_10.dat");
+        assert_eq!(
+            parse_backup_name(name.strip_suffix(".dat").unwrap()),
+            Some((date, 10))
+        );
+        assert_eq!(parse_backup_name("2025-10-26_01-30-00_UTC_x"), None);
+        assert_eq!(parse_backup_name("2025-10-26_01-30-00"), None);
+        assert_eq!(parse_backup_name("2025-10-26_01-30-00_2"), None);
+    }
+}
@@ -0,0 +1,505 @@
+//! The editor without the window, to read and edit saves from scripts.
+
+use std::{
+    io::{Write, stdout},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+use pw2repac_save::{
+    SaveError, SaveFile,
+    backup::{DEFAULT_BACKUPS_KEPT, backup_folder, create_backup},
+    game_process::is_game_running,
+    save_data_info::{
+        FieldDef, SAVE_FIELDS, SAVE_FILE_HEADER_FIELDS, SaveDataIntType, SaveDataVar, SaveFileData,
+    },
+    save_date::parse_save_date,
+    save_file_parser::{
+        SFigureDisplayInfo, find_account_save_file, get_figure_info_from_save_data,
+        get_float_value_from_save_data, get_int_array_from_save_data, get_int_value_from_save_data,
+        get_text_value_from_save_data, modify_figure_info, modify_save_data,
+        modify_save_data_float, modify_save_data_text,
+    },
+};
+
+const USAGE: &str = "\
+Usage: pw2repac-cli [options] <command>
+
+Commands:
+  dump                      Print every variable of the slot, or of the global data without --slot
+  get <field> [index]       Print a variable, or one value of an array
+  set <field> [index] <value>
+                            Change a variable, or one value of an array, and save the file
+  create-slot <slot>        Put a brand new save in the slot and save the file
+  delete-slot <slot>        Mark the slot as empty and save the file
+
+Fields are found by their name in the game's code (m_iStockNum), their simple name (Lives) or
+their name in SaveDataVar (Lives), without caring about the case. Values of figures are written
+as <figure id>,<angle>.
+
+Options:
+  --path <file>             Save file to use instead of the game's
+  --account <id>            Steam user id of the save to use, when there are several
+  --slot <1-4>              Slot of the variables, they're the global data without it
+  --simple                  Print the simple names of the variables instead of the code ones
+  --backups <count>         Backups of the save file to keep, 0 turns them off (default 10)
+  --force                   Save even if the game is running";
+
+/// Options given in the command line.
+struct CliArgs {
+    path: Option<PathBuf>,
+    account: Option<String>,
+    slot: Option<u8>,
+    simple_names: bool,
+    backups: usize,
+    force: bool,
+    command: Command,
+}
+
+enum Command {
+    Dump,
+    Get {
+        field: String,
+        index: Option<usize>,
+    },
+    Set {
+        field: String,
+        index: Option<usize>,
+        value: String,
+    },
+    CreateSlot(u8),
+    DeleteSlot(u8),
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() || args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        print_lines(&[USAGE]);
+        return ExitCode::SUCCESS;
+    }
+    match parse_args(args).and_then(run) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse_args(args: Vec<String>) -> Result<CliArgs, String> {
+    let mut path = None;
+    let mut account = None;
+    let mut slot = None;
+    let mut simple_names = false;
+    let mut backups = DEFAULT_BACKUPS_KEPT;
+    let mut force = false;
+    let mut positional = vec![];
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => (name.to_string(), Some(value)),
+            _ => (arg.clone(), None),
+        };
+        let mut option_value = || {
+            inline_value
+                .map(str::to_string)
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("{} needs a value\n\n{}", name, USAGE))
+        };
+        match name.as_str() {
+            "--path" => path = Some(PathBuf::from(option_value()?)),
+            "--account" => account = Some(option_value()?),
+            "--slot" => slot = Some(parse_slot(&option_value()?)?),
+            "--backups" => {
+                let count = option_value()?;
+                backups = count
+                    .parse()
+                    .map_err(|_| format!("\"{}\" is not a number of backups", count))?;
+            }
+            "--simple" => simple_names = true,
+            "--force" => force = true,
+            _ if arg.starts_with("--") => {
+                return Err(format!("unknown option {}\n\n{}", arg, USAGE));
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let wrong_arguments = || format!("wrong arguments\n\n{}", USAGE);
+    let parse_index = |index: &String| {
+        index
+            .parse::<usize>()
+            .map_err(|_| format!("\"{}\" is not an array index", index))
+    };
+    let command = match positional.as_slice() {
+        [command] if command == "dump" => Command::Dump,
+        [command, field] if command == "get" => Command::Get {
+            field: field.clone(),
+            index: None,
+        },
+        [command, field, index] if command == "get" => Command::Get {
+            field: field.clone(),
+            index: Some(parse_index(index)?),
+        },
+        [command, field, value] if command == "set" => Command::Set {
+            field: field.clone(),
+            index: None,
+            value: value.clone(),
+        },
+        [command, field, index, value] if command == "set" => Command::Set {
+            field: field.clone(),
+            index: Some(parse_index(index)?),
+            value: value.clone(),
+        },
+        [command, slot] if command == "create-slot" => Command::CreateSlot(parse_slot(slot)?),
+        [command, slot] if command == "delete-slot" => Command::DeleteSlot(parse_slot(slot)?),
+        [command] if command == "create-slot" || command == "delete-slot" => {
+            let slot = slot.ok_or_else(wrong_arguments)?;
+            if command == "create-slot" {
+                Command::CreateSlot(slot)
+            } else {
+                Command::DeleteSlot(slot)
+            }
+        }
+        _ => return Err(wrong_arguments()),
+    };
+
+    Ok(CliArgs {
+        path,
+        account,
+        slot,
+        simple_names,
+        backups,
+        force,
+        command,
+    })
+}
+
+fn parse_slot(slot: &str) -> Result<u8, String> {
+    match slot.parse::<u8>() {
+        Ok(slot) if (1..=4).contains(&slot) => Ok(slot),
+        _ => Err(format!("\"{}\" is not a slot, they go from 1 to 4", slot)),
+    }
+}
+
+fn run(cli_args: CliArgs) -> Result<(), String> {
+    let mut save_file = open_save_file(&cli_args).map_err(|e| e.to_string())?;
+
+    match &cli_args.command {
+        Command::Dump => {
+            let fields = match cli_args.slot {
+                Some(_) => SAVE_FIELDS,
+                None => SAVE_FILE_HEADER_FIELDS,
+            };
+            let mut lines = vec![];
+            for field in fields {
+                if field.var.min_version() > save_file.version() {
+                    continue;
+                }
+                // the code names of the global data aren't known
+                let name = match field.variable_name {
+                    Some(name) if !cli_args.simple_names => name,
+                    _ => field.variable_name_simple,
+                };
+                let values = field_data(field, cli_args.slot)
+                    .and_then(|var_data| read_values(save_file.as_bytes(), &var_data));
+                match values {
+                    Ok(values) if is_array(field.int_type) => {
+                        for (i, value) in values.iter().enumerate() {
+                            lines.push(format!("{}[{}] = {}", name, i, value));
+                        }
+                    }
+                    Ok(values) => lines.push(format!("{} = {}", name, values.join(" "))),
+                    Err(e) => lines.push(format!("{} = <{}>", name, e)),
+                }
+            }
+            print_lines(&lines);
+            Ok(())
+        }
+        Command::Get { field, index } => {
+            let field = find_field(field, cli_args.slot)?;
+            let values = field_data(field, cli_args.slot)
+                .and_then(|var_data| read_values(save_file.as_bytes(), &var_data))
+                .map_err(|e| e.to_string())?;
+            match index {
+                Some(index) => {
+                    let value = values
+                        .get(*index)
+                        .filter(|_| is_array(field.int_type))
+                        .ok_or_else(|| {
+                            format!(
+                                "{} has no value at index {}",
+                                field.variable_name_simple, index
+                            )
+                        })?;
+                    print_lines(&[value]);
+                }
+                None => print_lines(&values),
+            }
+            Ok(())
+        }
+        Command::Set {
+            field,
+            index,
+            value,
+        } => {
+            let field = find_field(field, cli_args.slot)?;
+            if field.read_only {
+                return Err(format!(
+                    "{} is read only, what it does isn't known yet",
+                    field.variable_name_simple
+                ));
+            }
+            if field.var.min_version() > save_file.version() {
+                return Err(SaveError::NotInVersion {
+                    var: field.var,
+                    version: save_file.version(),
+                }
+                .to_string());
+            }
+            check_game_closed(&cli_args)?;
+            write_value(
+                save_file.as_bytes_mut(),
+                field,
+                &field_data(field, cli_args.slot).map_err(|e| e.to_string())?,
+                *index,
+                value,
+            )?;
+            save(&mut save_file, &cli_args)
+        }
+        Command::CreateSlot(slot) => {
+            check_game_closed(&cli_args)?;
+            save_file
+                .slot_mut(*slot)
+                .create()
+                .map_err(|e| e.to_string())?;
+            save(&mut save_file, &cli_args)
+        }
+        Command::DeleteSlot(slot) => {
+            check_game_closed(&cli_args)?;
+            save_file
+                .slot_mut(*slot)
+                .delete()
+                .map_err(|e| e.to_string())?;
+            save(&mut save_file, &cli_args)
+        }
+    }
+}
+
+/// Prints one line for each value, stopping quietly if the output is closed, like by `head`.
+fn print_lines<T: std::fmt::Display>(lines: &[T]) {
+    let mut out = stdout().lock();
+    for line in lines {
+        if writeln!(out, "{}", line).is_err() {
+            return;
+        }
+    }
+}
+
+/// The file given in the options, or the game's like the editor finds it.
+fn open_save_file(cli_args: &CliArgs) -> Result<SaveFile, SaveError> {
+    match (&cli_args.path, &cli_args.account) {
+        (Some(path), _) => SaveFile::from_path(path),
+        (None, Some(account)) => {
+            find_account_save_file(Some(account)).and_then(|found| SaveFile::from_path(found.path))
+        }
+        (None, None) => SaveFile::load(),
+    }
+}
+
+fn check_game_closed(cli_args: &CliArgs) -> Result<(), String> {
+    if !cli_args.force && is_game_running() {
+        return Err(
+            "the game is running and would write over the changes, close it or use --force"
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
+/// Writes the file where it was read from, after backing up the one on disk like the editor.
+fn save(save_file: &mut SaveFile, cli_args: &CliArgs) -> Result<(), String> {
+    if let Some(path) = save_file.path() {
+        backup_folder(path)
+            .and_then(|folder| create_backup(path, &folder, cli_args.backups))
+            .map_err(|e| format!("could not back up the save file, it was not saved: {}", e))?;
+    }
+    save_file.write().map_err(|e| e.to_string())
+}
+
+fn field_data(field: &FieldDef, slot: Option<u8>) -> Result<SaveFileData, SaveError> {
+    match slot {
+        Some(slot) => field.at_slot(slot),
+        None => Ok(field.at_header()),
+    }
+}
+
+/// Finds a variable by any of its names, among the slot ones or the global ones without a slot.
+fn find_field(name: &str, slot: Option<u8>) -> Result<&'static FieldDef, String> {
+    let matches = |field: &&FieldDef| {
+        field
+            .variable_name
+            .is_some_and(|variable_name| variable_name.eq_ignore_ascii_case(name))
+            || field.variable_name_simple.eq_ignore_ascii_case(name)
+            || format!("{:?}", field.var).eq_ignore_ascii_case(name)
+    };
+    let fields = match slot {
+        Some(_) => SAVE_FIELDS,
+        None => SAVE_FILE_HEADER_FIELDS,
+    };
+    if let Some(field) = fields.iter().find(matches) {
+        return Ok(field);
+    }
+    if slot.is_none() && SAVE_FIELDS.iter().any(|field| matches(&field)) {
+        return Err(format!("{} is in the slots, pick one with --slot", name));
+    }
+    Err(format!("there's no variable called {}", name))
+}
+
+fn is_array(int_type: SaveDataIntType) -> bool {
+    array_len(int_type).is_some()
+}
+
+/// How many values the array has, `None` for types with a single value.
+fn array_len(int_type: SaveDataIntType) -> Option<u32> {
+    match int_type {
+        SaveDataIntType::Arrayi32(len)
+        | SaveDataIntType::Arrayu8(len)
+        | SaveDataIntType::BitfieldArray(len)
+        | SaveDataIntType::SFigureDisplayInfoArray(len) => Some(len),
+        _ => None,
+    }
+}
+
+/// The values of a variable as text, one for every value of arrays.
+fn read_values(save_data: &[u8], var_data: &SaveFileData) -> Result<Vec<String>, SaveError> {
+    let (slot_base, offset, int_type) =
+        (var_data.slot_base_add, var_data.offset, &var_data.int_type);
+    match int_type {
+        SaveDataIntType::Bool
+        | SaveDataIntType::U32
+        | SaveDataIntType::I32
+        | SaveDataIntType::Bitfield => Ok(vec![
+            get_int_value_from_save_data(save_data, slot_base, offset, int_type)?.to_string(),
+        ]),
+        SaveDataIntType::F32 => Ok(vec![
+            get_float_value_from_save_data(save_data, slot_base, offset, int_type)?.to_string(),
+        ]),
+        SaveDataIntType::Arrayi32(_)
+        | SaveDataIntType::Arrayu8(_)
+        | SaveDataIntType::BitfieldArray(_) => Ok(get_int_array_from_save_data(
+            save_data, slot_base, offset, int_type,
+        )?
+        .iter()
+        .map(i64::to_string)
+        .collect()),
+        SaveDataIntType::ArrayText(_) => Ok(vec![
+            get_text_value_from_save_data(save_data, slot_base, offset, int_type)?
+                .trim_end_matches('\0')
+                .to_string(),
+        ]),
+        SaveDataIntType::SFigureDisplayInfoArray(len) => Ok(get_figure_info_from_save_data(
+            save_data, slot_base, offset, *len,
+        )?
+        .iter()
+        .map(|figure_info| format!("{},{}", figure_info.figure_id, figure_info.angle))
+        .collect()),
+    }
+}
+
+/// Parses a whole number, in hexadecimal with `0x`, or the name of one of the variable's values.
+fn parse_int(field: &FieldDef, value: &str) -> Result<i64, String> {
+    let parsed = match value.strip_prefix("0x") {
+        Some(hex) => i64::from_str_radix(hex, 16),
+        None => value.parse(),
+    };
+    parsed.or_else(|_| {
+        field
+            .values
+            .and_then(|values| {
+                values
+                    .iter()
+                    .find(|(_, name)| name.eq_ignore_ascii_case(value))
+                    .map(|(value, _)| *value)
+            })
+            .ok_or_else(|| format!("\"{}\" is not a number", value))
+    })
+}
+
+fn write_value(
+    save_data: &mut [u8],
+    field: &FieldDef,
+    var_data: &SaveFileData,
+    index: Option<usize>,
+    value: &str,
+) -> Result<(), String> {
+    let (slot_base, offset, int_type) =
+        (var_data.slot_base_add, var_data.offset, var_data.int_type);
+    let name = field.variable_name_simple;
+
+    let index = match (array_len(int_type), index) {
+        (Some(len), Some(index)) if index < len as usize => index,
+        (Some(len), Some(index)) => {
+            return Err(format!(
+                "{} has {} values, there's no index {}",
+                name, len, index
+            ));
+        }
+        (Some(_), None) => return Err(format!("{} is an array, give the index to set", name)),
+        (None, Some(_)) => return Err(format!("{} is not an array", name)),
+        (None, None) => 0,
+    };
+
+    let res = match int_type {
+        SaveDataIntType::Bool
+        | SaveDataIntType::U32
+        | SaveDataIntType::I32
+        | SaveDataIntType::Bitfield => modify_save_data(
+            save_data,
+            slot_base,
+            offset,
+            int_type,
+            parse_int(field, value)?,
+        ),
+        SaveDataIntType::Arrayi32(len)
+        | SaveDataIntType::Arrayu8(len)
+        | SaveDataIntType::BitfieldArray(len) => {
+            let byte_size = int_type.size() / len;
+            modify_save_data(
+                save_data,
+                slot_base,
+                offset + index as u32 * byte_size,
+                int_type,
+                parse_int(field, value)?,
+            )
+        }
+        SaveDataIntType::F32 => {
+            let value: f32 = value
+                .parse()
+                .map_err(|_| format!("\"{}\" is not a number", value))?;
+            modify_save_data_float(save_data, slot_base, offset, value)
+        }
+        SaveDataIntType::ArrayText(_) => {
+            if field.var == SaveDataVar::SaveDate {
+                parse_save_date(value, (slot_base + offset) as usize).map_err(|e| e.to_string())?;
+            }
+            modify_save_data_text(save_data, slot_base, offset, int_type, value)
+        }
+        SaveDataIntType::SFigureDisplayInfoArray(_) => {
+            let figure_info = value
+                .split_once(',')
+                .and_then(|(figure_id, angle)| {
+                    Some(SFigureDisplayInfo {
+                        figure_id: figure_id.trim().parse().ok()?,
+                        angle: angle.trim().parse().ok()?,
+                    })
+                })
+                .ok_or_else(|| {
+                    format!("\"{}\" is not a figure, write it as <id>,<angle>", value)
+                })?;
+            modify_figure_info(save_data, slot_base, offset, int_type, index, figure_info)
+        }
+    };
+    res.map_err(|e| e.to_string())
+}
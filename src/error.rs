@@ -0,0 +1,123 @@
+use std::{fmt, io, path::PathBuf};
+
+use crate::{
+    save_data_info::{SaveDataIntType, SaveDataVar},
+    version::SaveVersion,
+};
+
+/// Everything that can go wrong when finding, reading, parsing or writing a save file.
+#[derive(Debug)]
+pub enum SaveError {
+    /// The game's save folder or save file could not be found.
+    PathNotFound(String),
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    SizeMismatch {
+        expected: usize,
+        found: usize,
+    },
+    /// Slots go from 1 to 4.
+    InvalidSlot(u8),
+    /// The file read back after writing doesn't have the bytes that were written.
+    WriteNotVerified {
+        path: PathBuf,
+    },
+    /// A read or write would go past the end of the save data.
+    OutOfRange {
+        address: usize,
+        len: usize,
+        data_size: usize,
+    },
+    InvalidValue {
+        address: usize,
+        reason: String,
+    },
+    /// The value at this address can't be read or written as this type by the function used.
+    UnsupportedType {
+        address: usize,
+        int_type: SaveDataIntType,
+    },
+    /// Both variables changed the same bytes of a slot, only one of them can be written.
+    OverlappingChanges {
+        var: SaveDataVar,
+        other: SaveDataVar,
+    },
+    /// The variable is in the file header, it can't be read or written through a save slot.
+    NotInSlot {
+        var: SaveDataVar,
+    },
+    /// The variable was added by a later update than the one that wrote the save file.
+    NotInVersion {
+        var: SaveDataVar,
+        version: SaveVersion,
+    },
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::PathNotFound(reason) => write!(f, "Save file not found: {}", reason),
+            SaveError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            SaveError::SizeMismatch { expected, found } => write!(
+                f,
+                "Save file has the wrong size, expected {} bytes but found {}",
+                expected, found
+            ),
+            SaveError::InvalidSlot(slot) => {
+                write!(f, "There's no save slot {}, they go from 1 to 4", slot)
+            }
+            SaveError::WriteNotVerified { path } => write!(
+                f,
+                "{}: the file read back after saving is different from what was written",
+                path.display()
+            ),
+            SaveError::OutOfRange {
+                address,
+                len,
+                data_size,
+            } => write!(
+                f,
+                "Address {:X} (+{} bytes) is out of range, the save data is {:X} bytes long",
+                address, len, data_size
+            ),
+            SaveError::InvalidValue { address, reason } => {
+                write!(f, "Invalid value at address {:X}: {}", address, reason)
+            }
+            SaveError::UnsupportedType { address, int_type } => write!(
+                f,
+                "Value at address {:X} can't be used as {} here",
+                address,
+                int_type.name()
+            ),
+            SaveError::OverlappingChanges { var, other } => write!(
+                f,
+                "{} and {} share bytes in the save, only one of them can be changed at a time",
+                other.field().variable_name_simple,
+                var.field().variable_name_simple
+            ),
+            SaveError::NotInSlot { var } => write!(
+                f,
+                "{} is in the file header, not in a save slot",
+                var.field().variable_name_simple
+            ),
+            SaveError::NotInVersion { var, version } => write!(
+                f,
+                "{} isn't in saves of the {}, it was added in the {}",
+                var.field().variable_name_simple,
+                version,
+                var.min_version()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SaveError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use crate::steam::REPAC_APP_ID;
+
+/// Part of the path of the game's executable, matched without caring about the case. It's also
+/// the name of the game's folder in `steamapps/common`.
+const REPAC_EXE_NAME: &str = "pac-man world 2 re-pac";
+
+/// Whether a process is the game, from the contents of its `/proc/<pid>/cmdline` and
+/// `/proc/<pid>/environ`. Under Proton the game runs as a Windows `.exe` from its own folder,
+/// which Wine shows as the first argument. Wine and Steam start their own helpers with the
+/// game's `SteamAppId` too, so it's only used to leave out the processes of other games.
+pub fn is_repac_process(cmdline: &[u8], environ: &[u8]) -> bool {
+    let cmdline = String::from_utf8_lossy(cmdline).to_lowercase();
+    // the launchers around it, like Steam's `reaper`, have the game's path in later arguments
+    let is_repac_exe = cmdline
+        .split('\0')
+        .next()
+        .is_some_and(|exe| exe.contains(REPAC_EXE_NAME) && exe.ends_with(".exe"));
+    let app_id = format!("SteamAppId={}", REPAC_APP_ID).into_bytes();
+    let is_other_app = environ
+        .split(|byte| *byte == 0)
+        .any(|variable| variable.starts_with(b"SteamAppId=") && variable != app_id.as_slice());
+    is_repac_exe && !is_other_app
+}
+
+/// Whether the game is running, by looking at every process in `/proc`. The game keeps the save
+/// it read at launch and writes it over ours when it saves, so edits shouldn't be saved meanwhile.
+#[cfg(target_os = "linux")]
+pub fn is_game_running() -> bool {
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return false;
+    };
+    let own_pid = std::process::id().to_string();
+    entries.flatten().any(|entry| {
+        let file_name = entry.file_name();
+        let Some(pid) = file_name.to_str() else {
+            return false;
+        };
+        if pid == own_pid || !pid.bytes().all(|byte| byte.is_ascii_digit()) {
+            return false;
+        }
+        // processes of other users can't be read, the game would be ours anyway
+        let Ok(cmdline) = std::fs::read(entry.path().join("cmdline")) else {
+            return false;
+        };
+        let environ = std::fs::read(entry.path().join("environ")).unwrap_or_default();
+        is_repac_process(&cmdline, &environ)
+    })
+}
+
+/// Only Linux is checked for now.
+#[cfg(not(target_os = "linux"))]
+pub fn is_game_running() -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_game_under_proton() {
+        let game = b"Z:\\home\\pac\\.local\\share\\Steam\\steamapps\\common\\PAC-MAN WORLD 2 Re-PAC\\PAC-MAN WORLD 2 Re-PAC.exe\0";
+        assert!(is_repac_process(game, b""));
+        assert!(is_repac_process(
+            game,
+            b"HOME=/home/pac\0SteamAppId=2324290\0"
+        ));
+        // other games and the Steam and Wine tools around the game, which get its app id too
+        assert!(!is_repac_process(
+            b"C:\\Game\\Other.exe\0",
+            b"SteamAppId=228980\0"
+        ));
+        assert!(!is_repac_process(game, b"SteamAppId=228980\0"));
+        for helper in [
+            b"C:\\windows\\system32\\steam.exe\0".as_slice(),
+            b"C:\\windows\\explorer.exe\0/desktop\0",
+            b"C:\\windows\\system32\\services.exe\0",
+            b"C:\\windows\\system32\\winedevice.exe\0",
+        ] {
+            assert!(!is_repac_process(helper, b"SteamAppId=2324290\0"));
+        }
+        assert!(!is_repac_process(
+            b"/home/pac/.local/share/Steam/ubuntu12_32/reaper\0SteamLaunch\0AppId=2324290\0--\0/home/pac/.local/share/Steam/steamapps/common/PAC-MAN WORLD 2 Re-PAC/PAC-MAN WORLD 2 Re-PAC.exe\0",
+            b"SteamAppId=2324290\0"
+        ));
+        assert!(!is_repac_process(b"vim\0notes-about-pac-man.txt\0", b""));
+    }
+}
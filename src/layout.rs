@@ -0,0 +1,337 @@
+use std::fmt;
+
+use crate::save_data_info::{
+    FieldDef, SAVE_FIELDS, SAVE_FILE_HEADER_FIELDS, SAVE_FILE_HEADER_SIZE, SAVE_SLOT_SIZE,
+    SaveDataVar,
+};
+
+/// Variables that are stored one after the other with the same distance between them,
+/// each group should be evenly spaced in the save slot.
+pub static RELATED_FIELDS: &[&[SaveDataVar]] = &[
+    &[
+        SaveDataVar::PlayTimeHours,
+        SaveDataVar::PlayTimeMinutes,
+        SaveDataVar::PlayTimeSeconds,
+    ],
+    &[
+        SaveDataVar::StageCherryFlag,
+        SaveDataVar::StageStrawberryFlag,
+        SaveDataVar::StageOrangeFlag,
+        SaveDataVar::StageAppleFlag,
+        SaveDataVar::StageMelonFlag,
+        SaveDataVar::StageCherryNum,
+        SaveDataVar::StageStrawberryNum,
+        SaveDataVar::StageOrangeNum,
+        SaveDataVar::StageAppleNum,
+        SaveDataVar::StageMelonNum,
+    ],
+    &[
+        SaveDataVar::VillageCherryFlag,
+        SaveDataVar::VillageStrawberryFlag,
+        SaveDataVar::VillageOrangeFlag,
+        SaveDataVar::VillageAppleFlag,
+        SaveDataVar::VillageMelonFlag,
+    ],
+    &[
+        SaveDataVar::VillageCherryGetNum,
+        SaveDataVar::VillageStrawberryGetNum,
+        SaveDataVar::VillageOrangeGetNum,
+        SaveDataVar::VillageAppleGetNum,
+        SaveDataVar::VillageMelonGetNum,
+    ],
+    &[SaveDataVar::KeyConfigP1, SaveDataVar::KeyConfigP2],
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutIssueKind {
+    /// The variable shares bytes with another one.
+    Overlap { other: SaveDataVar },
+    /// The variable ends after the start of the next save slot, or the first one for the header.
+    PastSectionEnd { end: u32, section_size: u32 },
+    /// The variable isn't where the spacing of its group says it should be.
+    IrregularSpacing { expected_offset: u32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayoutIssue {
+    pub var: SaveDataVar,
+    pub kind: LayoutIssueKind,
+}
+
+impl fmt::Display for LayoutIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let field = self.var.field();
+        let end = field.offset + field.int_type.size();
+        match self.kind {
+            LayoutIssueKind::Overlap { other } => {
+                let other_field = other.field();
+                write!(
+                    f,
+                    "{:?} ({:X}..{:X}) overlaps {:?} ({:X}..{:X})",
+                    self.var,
+                    field.offset,
+                    end,
+                    other,
+                    other_field.offset,
+                    other_field.offset + other_field.int_type.size()
+                )
+            }
+            LayoutIssueKind::PastSectionEnd { end, section_size } => write!(
+                f,
+                "{:?} ({:X}..{:X}) goes past the section size {:X}",
+                self.var, field.offset, end, section_size
+            ),
+            LayoutIssueKind::IrregularSpacing { expected_offset } => write!(
+                f,
+                "{:?} is at {:X} but the spacing of its group puts it at {:X}",
+                self.var, field.offset, expected_offset
+            ),
+        }
+    }
+}
+
+/// Checks every variable in `SAVE_FIELDS` and `SAVE_FILE_HEADER_FIELDS` against the others of
+/// its table and the size of its section.
+pub fn verify_layout() -> Vec<LayoutIssue> {
+    let mut issues = vec![];
+    verify_section(SAVE_FIELDS, SAVE_SLOT_SIZE, &mut issues);
+    verify_section(SAVE_FILE_HEADER_FIELDS, SAVE_FILE_HEADER_SIZE, &mut issues);
+
+    for group in RELATED_FIELDS {
+        let [first, second, ..] = group else {
+            continue;
+        };
+        let start = first.field().offset;
+        let step = second.field().offset.wrapping_sub(start);
+        for (i, var) in group.iter().enumerate().skip(2) {
+            let expected_offset = start.wrapping_add(step.wrapping_mul(i as u32));
+            if var.field().offset != expected_offset {
+                issues.push(LayoutIssue {
+                    var: *var,
+                    kind: LayoutIssueKind::IrregularSpacing { expected_offset },
+                });
+            }
+        }
+    }
+
+    issues
+}
+
+fn verify_section(fields: &[FieldDef], section_size: u32, issues: &mut Vec<LayoutIssue>) {
+    let mut fields_by_offset: Vec<&FieldDef> = fields.iter().collect();
+    fields_by_offset.sort_by_key(|field| field.offset);
+
+    for (i, field) in fields_by_offset.iter().enumerate() {
+        let end = field.offset + field.int_type.size();
+        if end > section_size {
+            issues.push(LayoutIssue {
+                var: field.var,
+                kind: LayoutIssueKind::PastSectionEnd { end, section_size },
+            });
+        }
+        for other in fields_by_offset[i + 1..]
+            .iter()
+            .take_while(|other| other.offset < end)
+        {
+            issues.push(LayoutIssue {
+                var: field.var,
+                kind: LayoutIssueKind::Overlap { other: other.var },
+            });
+        }
+    }
+}
+
+/// A part of the save file that can be compared and restored on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveSection {
+    Header,
+    Slot(u8),
+    SystemArea,
+}
+
+impl SaveSection {
+    pub fn name(&self) -> String {
+        match self {
+            SaveSection::Header => "Global Data".to_string(),
+            SaveSection::Slot(slot) => format!("Save {}", slot),
+            SaveSection::SystemArea => "System Area".to_string(),
+        }
+    }
+}
+
+/// Bytes of a save slot described by the same variables, or by none when they're unknown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlotRange {
+    /// Offset from the start of the slot.
+    pub start: u32,
+    pub end: u32,
+    pub vars: Vec<SaveDataVar>,
+}
+
+impl SlotRange {
+    pub fn is_known(&self) -> bool {
+        !self.vars.is_empty()
+    }
+
+    /// Offsets of the bytes of the range that aren't 0 in this slot.
+    pub fn non_zero_bytes(&self, slot_bytes: &[u8]) -> Vec<u32> {
+        (self.start..self.end)
+            .filter(|offset| {
+                slot_bytes
+                    .get(*offset as usize)
+                    .is_some_and(|byte| *byte != 0)
+            })
+            .collect()
+    }
+}
+
+/// Splits a save slot in the ranges described by `SAVE_FIELDS` and the unknown ones between them.
+pub fn slot_coverage() -> Vec<SlotRange> {
+    let mut bounds: Vec<u32> = SAVE_FIELDS
+        .iter()
+        .flat_map(|field| [field.offset, field.offset + field.int_type.size()])
+        .chain([0, SAVE_SLOT_SIZE])
+        .filter(|bound| *bound <= SAVE_SLOT_SIZE)
+        .collect();
+    bounds.sort();
+    bounds.dedup();
+
+    let mut ranges: Vec<SlotRange> = vec![];
+    for pair in bounds.windows(2) {
+        let (start, end) = (pair[0], pair[1]);
+        let vars: Vec<SaveDataVar> = SAVE_FIELDS
+            .iter()
+            .filter(|field| field.offset <= start && end <= field.offset + field.int_type.size())
+            .map(|field| field.var)
+            .collect();
+        match ranges.last_mut() {
+            Some(last) if last.vars == vars => last.end = end,
+            _ => ranges.push(SlotRange { start, end, vars }),
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::SaveError;
+    use crate::save_data_info::{
+        SAVE_SLOT_BASE_ADDRESSES, SYSTEM_AREA_START, get_save_slot_base_add,
+    };
+
+    /// Arrays whose length in the table is known to be too long, they overlap what comes after
+    /// them but the real length is still unverified.
+    const KNOWN_BAD_LENGTHS: [SaveDataVar; 4] = [
+        SaveDataVar::TimeTrialList,
+        SaveDataVar::FriendsTalkList,
+        SaveDataVar::GashaFlag,
+        SaveDataVar::FigureDisplayInfoCollab,
+    ];
+
+    /// Variables whose offset in the table looks wrong to the verifier, they're kept as they are
+    /// until a real save shows where they are.
+    const KNOWN_BAD_OFFSETS: [SaveDataVar; 3] = [
+        SaveDataVar::StageAppleNum,
+        SaveDataVar::OriginalFlag,
+        SaveDataVar::DroneSkinFlag,
+    ];
+
+    fn is_known_issue(issue: &LayoutIssue) -> bool {
+        let other = match issue.kind {
+            LayoutIssueKind::Overlap { other } => Some(other),
+            _ => None,
+        };
+        KNOWN_BAD_LENGTHS.contains(&issue.var)
+            || [Some(issue.var), other]
+                .into_iter()
+                .flatten()
+                .any(|var| KNOWN_BAD_OFFSETS.contains(&var))
+    }
+
+    #[test]
+    fn slot_stride_matches_slot_size() {
+        for pair in SAVE_SLOT_BASE_ADDRESSES.windows(2) {
+            assert_eq!(pair[1] - pair[0], SAVE_SLOT_SIZE);
+        }
+    }
+
+    #[test]
+    fn header_ends_at_first_slot() {
+        assert_eq!(get_save_slot_base_add(1).unwrap(), SAVE_FILE_HEADER_SIZE);
+    }
+
+    #[test]
+    fn system_area_starts_after_last_slot() {
+        assert_eq!(
+            get_save_slot_base_add(4).unwrap() + SAVE_SLOT_SIZE,
+            SYSTEM_AREA_START
+        );
+        assert!((SYSTEM_AREA_START as usize) < crate::EXPECTED_SAVE_FILE_SIZE);
+    }
+
+    #[test]
+    fn slots_go_from_1_to_4() {
+        assert!(matches!(
+            get_save_slot_base_add(0),
+            Err(SaveError::InvalidSlot(0))
+        ));
+        assert!(matches!(
+            get_save_slot_base_add(5),
+            Err(SaveError::InvalidSlot(5))
+        ));
+    }
+
+    #[test]
+    fn field_table_is_in_enum_order() {
+        for (i, field) in SAVE_FIELDS
+            .iter()
+            .chain(SAVE_FILE_HEADER_FIELDS)
+            .enumerate()
+        {
+            assert_eq!(field.var as usize, i);
+            assert_eq!(field.var.field().offset, field.offset);
+        }
+    }
+
+    #[test]
+    fn layout_has_no_new_issues() {
+        let issues: Vec<String> = verify_layout()
+            .into_iter()
+            .filter(|issue| !is_known_issue(issue))
+            .map(|issue| issue.to_string())
+            .collect();
+        assert!(issues.is_empty(), "layout issues:\n{}", issues.join("\n"));
+    }
+
+    #[test]
+    fn coverage_spans_the_whole_slot() {
+        let ranges = slot_coverage();
+        assert_eq!(ranges.first().map(|range| range.start), Some(0));
+        assert_eq!(ranges.last().map(|range| range.end), Some(SAVE_SLOT_SIZE));
+        for pair in ranges.windows(2) {
+            assert_eq!(pair[0].end, pair[1].start);
+        }
+        // m_bNameList is skipped, it's between FileExists and SaveDate
+        let name_list = ranges.iter().find(|range| range.start == 1).unwrap();
+        assert!(!name_list.is_known());
+        assert_eq!(name_list.end, SaveDataVar::SaveDate.field().offset);
+    }
+
+    #[test]
+    fn verifier_reports_overlaps() {
+        let issues = verify_layout();
+        assert!(issues.contains(&LayoutIssue {
+            var: SaveDataVar::TimeTrialList,
+            kind: LayoutIssueKind::Overlap {
+                other: SaveDataVar::TimeTrialCoopList
+            },
+        }));
+        assert!(issues.contains(&LayoutIssue {
+            var: SaveDataVar::StageAppleNum,
+            kind: LayoutIssueKind::IrregularSpacing {
+                expected_offset: 0x11FC
+            },
+        }));
+    }
+}
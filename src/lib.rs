@@ -0,0 +1,20 @@
+pub mod backup;
+pub mod error;
+pub mod game_process;
+pub mod layout;
+pub mod merge;
+pub mod new_file;
+pub mod save_data_info;
+pub mod save_date;
+pub mod save_file;
+pub mod save_file_parser;
+pub mod slot_data;
+pub mod steam;
+pub mod version;
+
+pub use error::SaveError;
+pub use save_file::{SaveFile, SaveSlot, SaveSlotMut};
+pub use slot_data::SlotData;
+pub use version::SaveVersion;
+
+pub const EXPECTED_SAVE_FILE_SIZE: usize = 176_608;
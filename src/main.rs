@@ -1,43 +1,52 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
-mod new_file;
-mod save_data_info;
-mod save_file_parser;
 
+use chrono::Local;
 use eframe::egui::{
     self, CentralPanel, Context, FontId, IconData, Key, TextStyle, TopBottomPanel, Ui,
 };
 use egui_extras::{Column, TableBuilder};
-use save_data_info::SaveDataVar;
-use save_file_parser::{get_save_file_variable, read_save_file};
-use std::sync::{LazyLock, Mutex};
-
-use crate::{
-    new_file::get_new_save_file,
+use pw2repac_save::{
+    SaveError, SaveFile, SaveSlot, SaveVersion,
+    backup::{
+        Backup, DEFAULT_BACKUPS_KEPT, SectionDiff, backup_folder, create_backup, diff_save_files,
+        list_backups, prune_backups,
+    },
+    game_process::is_game_running,
+    layout::{slot_coverage, verify_layout},
+    merge::{MergeItem, MergeSide},
     save_data_info::{
-        SaveDataIntType, SaveFileData, array_index_to_input_type, bgm_music_str_to_name,
-        bgm_music_str_to_name_collab, costume_int_to_name, get_save_slot_base_add,
+        self, SAVE_FIELDS, SAVE_FILE_HEADER_FIELDS, SAVE_SLOT_SIZE, SYSTEM_AREA_START,
+        SaveDataIntType, SaveDataVar, SaveFileData, array_index_to_input_type, bitfield_to_bits,
         int_to_controller_btn, int_to_key, int_to_maze_name, int_to_mission_level,
-        int_to_stage_name,
+        int_to_stage_name, value_name,
     },
+    save_date::{format_save_date, parse_save_date, save_date_now},
     save_file_parser::{
-        get_all_save_file_vars, get_basic_save_file_vars, get_figure_info_from_save_data,
-        get_int_array_from_save_data, get_int_value_from_save_data, get_text_value_from_save_data,
-        modify_save_data, modify_save_data_float, write_save_file,
+        SFigureDisplayInfo, SaveLocation, find_account_save_file, find_save_files,
+        get_env_file_path, get_figure_info_from_save_data, get_float_value_from_save_data,
+        get_int_array_from_save_data, get_int_array_value_from_save_data,
+        get_int_value_from_save_data, get_text_value_from_save_data, modify_figure_info,
+        modify_save_data, modify_save_data_float, modify_save_data_text,
     },
 };
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
 
-pub const EXPECTED_SAVE_FILE_SIZE: usize = 176_608;
-static SAVE_DATA: LazyLock<Mutex<Vec<u8>>> = LazyLock::new(|| {
-    // this closure runs only once, on the first access.
-    // it initializes the data inside the Mutex.
-    Mutex::new(vec![0; EXPECTED_SAVE_FILE_SIZE])
-});
+/// How often the running processes are checked for the game, and the save file for changes
+/// made by it.
+const CHECK_INTERVAL: Duration = Duration::from_secs(2);
 
 #[derive(Default)]
 enum CurrentMenu {
     #[default]
     Main,
     FileDetails,
+    LayoutReport,
+    SystemArea,
+    CoverageMap,
+    Backups,
 }
 
 #[derive(Default)]
@@ -47,8 +56,28 @@ enum SaveFileCurrentView {
     SingleArray(SaveFileData),
 }
 
+/// The part of a figure display info that is being edited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FigureField {
+    Id,
+    Angle,
+}
+
+struct FoundSave {
+    location: SaveLocation,
+    summary: String,
+}
+
 #[derive(Default)]
 struct App {
+    save_file: SaveFile,
+    /// Every save file found, the user can switch between them.
+    save_locations: Vec<FoundSave>,
+    show_save_picker_modal: bool,
+    show_open_modal: bool,
+    open_path_input: String,
+    /// Save file to open once the user confirms that the changes can be lost.
+    pending_save_path: Option<PathBuf>,
     current_view: CurrentMenu,
     single_save_file_view: SaveFileCurrentView,
     edit_mode: bool,
@@ -58,39 +87,195 @@ struct App {
     show_addresses: bool,
     show_simple_data_only: bool,
     show_combobox_when_possible: bool,
+    stamp_save_date: bool,
+    show_changed_system_rows_only: bool,
+    show_unknown_ranges_only: bool,
+    /// How many backups of the save file are kept, 0 turns them off.
+    backups_kept: usize,
+    /// Saving is refused while the game runs, instead of asking first.
+    block_save_while_game_runs: bool,
 
     save_slot_chosen: u8,
+    /// The details view shows the file header instead of `save_slot_chosen`.
+    global_data_chosen: bool,
     scroll_to_top: bool,
     edited_save_file: bool,
     show_confirm_exit_modal: bool,
     show_confirm_reload_modal: bool,
+    show_confirm_save_modal: bool,
+    error_message: Option<String>,
     current_user_input_selected: Option<SaveDataVar>,
     current_user_input_array_i_selected: Option<usize>,
+    /// Figure display info being edited, with its index in the array.
+    current_user_input_figure_selected: Option<(usize, FigureField)>,
     current_user_input: String,
+    /// Bitfield shown in the checkbox window, with the array index if it's in an array.
+    bitfield_editing: Option<(SaveFileData, Option<usize>)>,
+    backups: Vec<Backup>,
+    /// Backup compared with the open file in the backups view, with what's different.
+    backup_chosen: Option<(usize, Vec<SectionDiff>)>,
+    game_running: bool,
+    last_game_check: Option<Instant>,
+    last_disk_check: Option<Instant>,
+    /// The file as another program wrote it while it had edits, with what changed on each side.
+    disk_changes: Option<(SaveFile, Vec<MergeItem>)>,
+    show_merge_modal: bool,
+    /// Shown at the top until it's dismissed, like when the file was reloaded by itself.
+    file_notice: Option<String>,
 }
 
 impl eframe::App for App {
     fn update(&mut self, ctx: &eframe::egui::Context, _frame: &mut eframe::Frame) {
         set_styles(ctx);
-        self.show_top_bar(ctx);
+        // the views need the save file and &mut self at the same time, so the file is taken out
+        // of the app for the frame and put back at the end of it
+        let mut save_file = std::mem::take(&mut self.save_file);
+        self.check_game_running(ctx);
+        self.check_disk_changes(&mut save_file);
+        self.show_top_bar(ctx, &mut save_file);
+        self.show_game_running_banner(ctx);
+        self.show_disk_changes_banner(ctx);
         match self.current_view {
             CurrentMenu::Main => {
-                self.show_main_menu(ctx);
+                self.show_main_menu(ctx, &mut save_file);
             }
             CurrentMenu::FileDetails => {
                 match &self.single_save_file_view {
-                    SaveFileCurrentView::AllVars => self.show_details_save_file(ctx),
+                    SaveFileCurrentView::AllVars => {
+                        self.show_details_save_file(ctx, &mut save_file)
+                    }
                     SaveFileCurrentView::SingleArray(var_data) => {
-                        self.show_single_array_table(ctx, var_data.clone());
+                        self.show_single_array_table(ctx, *var_data, &mut save_file);
                     }
                 };
             }
+            CurrentMenu::LayoutReport => {
+                self.show_layout_report(ctx, &mut save_file);
+            }
+            CurrentMenu::SystemArea => {
+                self.show_system_area(ctx, &mut save_file);
+            }
+            CurrentMenu::CoverageMap => {
+                self.show_coverage_map(ctx, &mut save_file);
+            }
+            CurrentMenu::Backups => {
+                self.show_backups(ctx, &mut save_file);
+            }
         };
+        self.show_save_picker(ctx, &mut save_file);
+        self.show_open_file_modal(ctx, &mut save_file);
+        self.confirm_save_while_game_runs(ctx, &mut save_file);
+        self.show_merge_disk_changes(ctx, &mut save_file);
+        self.show_bitfield_modal(ctx, &mut save_file);
+        self.show_error_modal(ctx);
+        self.save_file = save_file;
+    }
+}
+
+/// Options given in the command line.
+#[derive(Default)]
+struct CliArgs {
+    /// Save file to open instead of looking for the game's.
+    path: Option<PathBuf>,
+    /// Steam user id of the save to open, for computers with saves of several accounts.
+    account: Option<String>,
+    /// How many backups to keep of the save file.
+    backups: Option<usize>,
+}
+
+fn parse_args() -> CliArgs {
+    let mut cli_args = CliArgs::default();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--account" {
+            cli_args.account = args.next();
+        } else if let Some(account) = arg.strip_prefix("--account=") {
+            cli_args.account = Some(account.to_string());
+        } else if arg == "--backups" {
+            cli_args.backups = args.next().and_then(|count| count.parse().ok());
+        } else if let Some(count) = arg.strip_prefix("--backups=") {
+            cli_args.backups = count.parse().ok();
+        } else if !arg.starts_with("--") && cli_args.path.is_none() {
+            cli_args.path = Some(PathBuf::from(arg));
+        } else {
+            println!("Unknown argument: {}", arg);
+            println!(
+                "Usage: pw2repac-save-editor [save file path] [--account <steam user id>] [--backups <count>]"
+            );
+        }
+    }
+    cli_args
+}
+
+/// Which slots of a save file are in use and their play time, to tell saves apart in the picker.
+fn save_file_summary(path: &Path) -> String {
+    let save_file = match SaveFile::from_path(path) {
+        Ok(save_file) => save_file,
+        Err(e) => return format!("Error: {}", e),
+    };
+    let slots: Vec<String> = (1..=4)
+        .filter(|slot| save_file.slot(*slot).exists().unwrap_or(false))
+        .map(|slot| match read_slot_summary(save_file.slot(slot)) {
+            Ok((_, play_time)) => format!("Save {} ({})", slot, play_time),
+            Err(_) => format!("Save {}", slot),
+        })
+        .collect();
+    if slots.is_empty() {
+        "No saves".to_string()
+    } else {
+        slots.join(", ")
     }
 }
 
 fn main() -> Result<(), eframe::Error> {
-    load_save_file();
+    let cli_args = parse_args();
+    let save_locations: Vec<FoundSave> = find_save_files()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|location| FoundSave {
+            summary: save_file_summary(&location.path),
+            location,
+        })
+        .collect();
+
+    let mut show_save_picker_modal = false;
+    let path_override = cli_args.path.or_else(get_env_file_path);
+    let loaded = match (path_override, &cli_args.account) {
+        // a path given by the user wins over the saves that were found
+        (Some(path), _) => SaveFile::from_path(path),
+        (None, Some(_)) => find_account_save_file(cli_args.account.as_deref())
+            .and_then(|location| SaveFile::from_path(location.path)),
+        (None, None) => {
+            let mut accounts: Vec<&str> = save_locations
+                .iter()
+                .map(|found| found.location.account.as_str())
+                .collect();
+            accounts.sort();
+            accounts.dedup();
+            if accounts.len() > 1 {
+                // don't guess whose save to edit
+                show_save_picker_modal = true;
+                Ok(SaveFile::empty())
+            } else {
+                match save_locations.first() {
+                    Some(found) => SaveFile::from_path(&found.location.path),
+                    None => SaveFile::load(),
+                }
+            }
+        }
+    };
+    let (save_file, error_message) = match loaded {
+        Ok(save_file) => (save_file, None),
+        Err(e) => {
+            println!("{}", e);
+            (
+                SaveFile::empty(),
+                Some(format!("Could not load the save file: {}", e)),
+            )
+        }
+    };
+
+    let backups_kept = cli_args.backups.unwrap_or(DEFAULT_BACKUPS_KEPT);
 
     let icon = load_icon();
 
@@ -109,6 +294,11 @@ fn main() -> Result<(), eframe::Error> {
         options,
         Box::new(|_ctx| {
             Ok(Box::<App>::new(App {
+                save_file,
+                save_locations,
+                show_save_picker_modal,
+                error_message,
+                backups_kept,
                 show_addresses: true,
                 show_simple_data_only: true,
                 show_combobox_when_possible: true,
@@ -118,21 +308,39 @@ fn main() -> Result<(), eframe::Error> {
     )
 }
 
-fn load_save_file() {
-    let save_file_data_read_res = read_save_file();
-
-    match save_file_data_read_res {
-        Ok(raw_bytes) => {
-            let mut vec_guard = SAVE_DATA.lock().unwrap();
-            vec_guard.clear();
-            vec_guard.extend(raw_bytes.iter());
-            Some(raw_bytes)
-        }
-        Err(err_mess) => {
-            println!("{}", err_mess);
-            None
-        }
+/// Lives and play time of a slot, as shown in the main menu.
+fn read_slot_summary(slot_data: SaveSlot) -> Result<(i64, String), SaveError> {
+    let lives = slot_data.get_int(SaveDataVar::Lives)?;
+    let hours = slot_data.get_int(SaveDataVar::PlayTimeHours)?;
+    let minutes = slot_data.get_int(SaveDataVar::PlayTimeMinutes)?;
+    let seconds = slot_data.get_int(SaveDataVar::PlayTimeSeconds)?;
+
+    let hours_str = if hours <= 9 {
+        format!("00{}", hours)
+    } else if hours <= 99 {
+        format!("0{}", hours)
+    } else {
+        hours.to_string()
+    };
+    let min_str = if minutes <= 9 {
+        format!("0{}", minutes)
+    } else {
+        minutes.to_string()
     };
+    let sec_str = if seconds <= 9 {
+        format!("0{}", seconds)
+    } else {
+        seconds.to_string()
+    };
+    Ok((lives, format!("{}:{}:{}", hours_str, min_str, sec_str)))
+}
+
+fn show_error_row(body: &mut egui_extras::TableBody, e: &SaveError) {
+    body.row(30.0, |mut row| {
+        row.col(|ui| {
+            ui.label(format!("Error: {}", e));
+        });
+    });
 }
 
 fn load_icon() -> IconData {
@@ -176,38 +384,95 @@ fn set_styles(ctx: &Context) {
 }
 
 impl App {
-    fn reload_save_file(&mut self) {
+    fn reload_save_file(&mut self, save_file: &mut SaveFile) {
+        if self.edited_save_file {
+            self.show_confirm_reload_modal = true;
+        } else {
+            self.load_save_file(save_file);
+        }
+    }
+
+    /// Opens another of the save files found, asks first if there are changes not saved.
+    fn open_save_location(&mut self, save_file: &mut SaveFile, path: PathBuf) {
+        self.pending_save_path = Some(path);
         if self.edited_save_file {
             self.show_confirm_reload_modal = true;
         } else {
-            load_save_file();
+            self.load_save_file(save_file);
         }
     }
 
-    fn show_top_bar(&mut self, ctx: &Context) {
+    /// Replaces the save data with the file on disk, keeps the current data if that fails.
+    fn load_save_file(&mut self, save_file: &mut SaveFile) -> bool {
+        let path = self
+            .pending_save_path
+            .take()
+            .or_else(|| save_file.path().map(Path::to_path_buf));
+        let loaded = match path {
+            Some(path) => SaveFile::from_path(path),
+            None => SaveFile::load(),
+        };
+        match loaded {
+            Ok(mut loaded) => {
+                // a version picked by hand is kept when the same file is read again
+                if loaded.path() == save_file.path()
+                    && let Some(version) = save_file.version_override()
+                {
+                    loaded.set_version(version);
+                }
+                *save_file = loaded;
+                self.disk_changes = None;
+                // the backups shown may be of the file that was open before
+                self.refresh_backups(save_file);
+                true
+            }
+            Err(e) => {
+                println!("{}", e);
+                self.error_message = Some(format!("Could not load the save file: {}", e));
+                false
+            }
+        }
+    }
+
+    fn show_top_bar(&mut self, ctx: &Context, save_file: &mut SaveFile) {
         TopBottomPanel::top("menu_bar").show(ctx, |ui| {
             egui::MenuBar::new().ui(ui, |ui| {
                 ui.menu_button("File", |ui| {
                     if ui.button("Reload Save Data").clicked() {
-                        self.reload_save_file();
+                        self.reload_save_file(save_file);
+                    }
+                    if ui.button("Open…").clicked() {
+                        self.show_open_modal = true;
+                        self.open_path_input = save_file
+                            .path()
+                            .map(|path| path.display().to_string())
+                            .unwrap_or_default();
+                    }
+                    if self.save_locations.len() > 1 && ui.button("Pick Save File").clicked() {
+                        self.show_save_picker_modal = true;
                     }
                     let save_text = if self.edited_save_file {
                         "Save Changes To File *"
                     } else {
                         "Save Changes To File  "
                     };
-                    if ui.button(save_text).clicked() {
-                        self.edited_save_file = false;
-                        let save_data_guard = SAVE_DATA.lock().unwrap();
-                        match write_save_file(save_data_guard.to_vec()) {
-                            Ok(_) => {
-                                println!("Save successful!");
-                            }
-                            Err(e) => {
-                                println!("ERROR: {}", e);
-                            }
+                    let save_blocked = self.game_running && self.block_save_while_game_runs;
+                    if ui
+                        .add_enabled(!save_blocked, egui::Button::new(save_text))
+                        .on_disabled_hover_text("The game is running, close it to save")
+                        .clicked()
+                    {
+                        if self.game_running {
+                            self.show_confirm_save_modal = true;
+                        } else {
+                            self.save_to_file(save_file);
                         }
                     }
+                    ui.checkbox(&mut self.stamp_save_date, "Stamp Date On Edited Slots");
+                    if ui.button("Backups").clicked() {
+                        self.current_view = CurrentMenu::Backups;
+                        self.refresh_backups(save_file);
+                    }
                     if ui.button("Exit").clicked() {
                         if self.edited_save_file {
                             self.show_confirm_exit_modal = true;
@@ -224,16 +489,367 @@ impl App {
                         &mut self.show_combobox_when_possible,
                         "Use Dropdown Menu When Applicable",
                     );
+                    ui.menu_button(format!("Save Version: {}", save_file.version()), |ui| {
+                        ui.label("Detected when the file is read, the one picked here is kept");
+                        for version in [SaveVersion::Original, SaveVersion::SonicUpdate] {
+                            if ui
+                                .radio(save_file.version() == version, version.name())
+                                .clicked()
+                            {
+                                save_file.set_version(version);
+                            }
+                        }
+                    });
+                    if ui.button("Layout Report").clicked() {
+                        self.current_view = CurrentMenu::LayoutReport;
+                    }
+                    if ui.button("Coverage Map").clicked() {
+                        self.current_view = CurrentMenu::CoverageMap;
+                        if !(1..=4).contains(&self.save_slot_chosen) {
+                            self.save_slot_chosen = 1;
+                        }
+                    }
+                    if ui.button("System Area").clicked() {
+                        self.current_view = CurrentMenu::SystemArea;
+                        self.current_user_input_array_i_selected = None;
+                    }
                 });
             });
         });
     }
 
-    fn show_main_menu(&mut self, ctx: &Context) {
+    /// Looks for the game in the running processes every few seconds.
+    fn check_game_running(&mut self, ctx: &Context) {
+        if self
+            .last_game_check
+            .is_none_or(|last_check| last_check.elapsed() >= CHECK_INTERVAL)
+        {
+            self.game_running = is_game_running();
+            self.last_game_check = Some(Instant::now());
+        }
+        ctx.request_repaint_after(CHECK_INTERVAL);
+    }
+
+    /// Looks every few seconds for changes to the save file made by another program, like the
+    /// game. The file is reloaded if it has no edits, otherwise the changes are merged.
+    fn check_disk_changes(&mut self, save_file: &mut SaveFile) {
+        if self.disk_changes.is_some()
+            || self
+                .last_disk_check
+                .is_some_and(|last_check| last_check.elapsed() < CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.last_disk_check = Some(Instant::now());
+
+        let disk = match save_file.read_disk_changes() {
+            Ok(Some(disk)) => disk,
+            Ok(None) => return,
+            Err(e) => {
+                // the file may be in the middle of being written, it's read again on the next check
+                println!("Could not check the save file for changes: {}", e);
+                return;
+            }
+        };
+        if self.edited_save_file {
+            let items = save_file.disk_merge_items(&disk);
+            self.disk_changes = Some((disk, items));
+            self.show_merge_modal = true;
+        } else {
+            // `disk` keeps the version picked by hand
+            *save_file = disk;
+            self.refresh_backups(save_file);
+            self.file_notice = Some(
+                "The save file was changed by another program, like the game, and was reloaded."
+                    .to_string(),
+            );
+        }
+    }
+
+    fn show_disk_changes_banner(&mut self, ctx: &Context) {
+        if self.file_notice.is_none() && self.disk_changes.is_none() {
+            return;
+        }
+        TopBottomPanel::top("disk_changes_banner").show(ctx, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                if self.disk_changes.is_some() {
+                    ui.colored_label(
+                        ui.visuals().warn_fg_color,
+                        "The save file was changed by another program while it had edits.",
+                    );
+                    if ui.button("Merge Changes").clicked() {
+                        self.show_merge_modal = true;
+                    }
+                } else if let Some(notice) = &self.file_notice {
+                    ui.label(notice);
+                    if ui.button("Ok").clicked() {
+                        self.file_notice = None;
+                    }
+                }
+            });
+        });
+    }
+
+    /// Lists what changed in the editor and on disk, and which side is kept of each change.
+    fn show_merge_disk_changes(&mut self, ctx: &Context, save_file: &mut SaveFile) {
+        if !self.show_merge_modal {
+            return;
+        }
+        let Some((_, items)) = &mut self.disk_changes else {
+            self.show_merge_modal = false;
+            return;
+        };
+
+        let mut merge_clicked = false;
+        let modal =
+            eframe::egui::Modal::new(eframe::egui::Id::new("Merge Disk Changes")).show(ctx, |ui| {
+                ui.set_width(700.0);
+
+                ui.heading("The Save File Changed On Disk");
+
+                ui.separator();
+
+                ui.label(
+                    "Another program, like the game, wrote the save file while it was being \
+                     edited. Pick which version of each change to keep, the changes made on one \
+                     side only are kept by default.",
+                );
+                let conflicts = items.iter().filter(|item| item.is_conflict()).count();
+                ui.label(format!(
+                    "{} changes, {} of them made on both sides.",
+                    items.len(),
+                    conflicts
+                ));
+
+                egui::ScrollArea::vertical()
+                    .id_salt("merge_items")
+                    .max_height(400.)
+                    .show(ui, |ui| {
+                        egui::Grid::new("merge_items_grid")
+                            .striped(true)
+                            .num_columns(4)
+                            .show(ui, |ui| {
+                                ui.strong("Section");
+                                ui.strong("Variables");
+                                ui.strong("Changed In");
+                                ui.strong("Keep");
+                                ui.end_row();
+                                for item in items.iter_mut() {
+                                    ui.label(item.section.name());
+                                    if item.vars.is_empty() {
+                                        ui.label(format!(
+                                            "{} unknown bytes at {:X}",
+                                            item.range.len(),
+                                            item.range.start
+                                        ));
+                                    } else {
+                                        ui.label(
+                                            item.vars
+                                                .iter()
+                                                .map(|var| var.field().variable_name_simple)
+                                                .collect::<Vec<&str>>()
+                                                .join(", "),
+                                        );
+                                    }
+                                    let changed_in =
+                                        match (item.changed_in_editor, item.changed_on_disk) {
+                                            (true, true) => "Both",
+                                            (true, false) => "Editor",
+                                            _ => "File",
+                                        };
+                                    if item.is_conflict() {
+                                        ui.colored_label(ui.visuals().warn_fg_color, changed_in);
+                                    } else {
+                                        ui.label(changed_in);
+                                    }
+                                    ui.horizontal(|ui| {
+                                        ui.radio_value(&mut item.side, MergeSide::Editor, "Mine");
+                                        ui.radio_value(&mut item.side, MergeSide::Disk, "File's");
+                                    });
+                                    ui.end_row();
+                                }
+                            });
+                    });
+
+                egui::Sides::new().show(
+                    ui,
+                    |ui| {
+                        if ui.button("Keep All Mine").clicked() {
+                            items
+                                .iter_mut()
+                                .for_each(|item| item.side = MergeSide::Editor);
+                        }
+                        if ui.button("Take All From File").clicked() {
+                            items
+                                .iter_mut()
+                                .for_each(|item| item.side = MergeSide::Disk);
+                        }
+                    },
+                    |ui| {
+                        if ui.button("Merge").clicked() {
+                            merge_clicked = true;
+                        }
+                        if ui.button("Decide Later").clicked() {
+                            ui.close();
+                        }
+                    },
+                );
+            });
+
+        if merge_clicked && let Some((disk, items)) = self.disk_changes.take() {
+            self.edited_save_file = items.iter().any(|item| item.side == MergeSide::Editor);
+            save_file.merge_disk_changes(disk, &items);
+            self.show_merge_modal = false;
+            if matches!(self.current_view, CurrentMenu::Backups) {
+                self.refresh_backups(save_file);
+            }
+        } else if modal.should_close() {
+            self.show_merge_modal = false;
+        }
+    }
+
+    /// Warns that the game is running, and lets the user choose if saving is refused or asked.
+    fn show_game_running_banner(&mut self, ctx: &Context) {
+        if !self.game_running {
+            return;
+        }
+        TopBottomPanel::top("game_running_banner").show(ctx, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                ui.colored_label(
+                    ui.visuals().warn_fg_color,
+                    "The game is running: it won't read the changes until it's restarted, and it \
+                     will write over them when it saves.",
+                );
+                ui.radio_value(
+                    &mut self.block_save_while_game_runs,
+                    false,
+                    "Ask Before Saving",
+                );
+                ui.radio_value(&mut self.block_save_while_game_runs, true, "Block Saving");
+            });
+        });
+    }
+
+    fn confirm_save_while_game_runs(&mut self, ctx: &Context, save_file: &mut SaveFile) {
+        if !self.show_confirm_save_modal {
+            return;
+        }
+
+        let modal =
+            eframe::egui::Modal::new(eframe::egui::Id::new("Confirm Save")).show(ctx, |ui| {
+                ui.set_width(350.0);
+
+                ui.heading("The Game Is Running, Save Anyway?");
+
+                ui.separator();
+
+                ui.label("The game will write over these changes the next time it saves.");
+
+                egui::Sides::new().show(
+                    ui,
+                    |_ui| {},
+                    |ui| {
+                        if ui.button("Save").clicked() {
+                            self.save_to_file(save_file);
+                            ui.close();
+                        }
+                        if ui.button("Cancel").clicked() {
+                            // This causes the current modals `should_close` to return true
+                            ui.close();
+                        }
+                    },
+                );
+            });
+
+        if modal.should_close() {
+            self.show_confirm_save_modal = false;
+        }
+    }
+
+    /// Writes the save file, after copying the one on disk to the backups.
+    fn save_to_file(&mut self, save_file: &mut SaveFile) {
+        // saving now would throw away what the other program wrote
+        if self.disk_changes.is_none() {
+            self.last_disk_check = None;
+            self.check_disk_changes(save_file);
+        }
+        if self.disk_changes.is_some() {
+            self.show_merge_modal = true;
+            return;
+        }
+        if let Some(path) = save_file.path()
+            && let Err(e) = backup_folder(path)
+                .and_then(|folder| create_backup(path, &folder, self.backups_kept))
+        {
+            println!("ERROR: {}", e);
+            self.error_message = Some(format!(
+                "Could not back up the save file, it was not saved: {}",
+                e
+            ));
+            return;
+        }
+        // the dates are stamped on a copy after the backup, so a failed save leaves the slots as
+        // they were
+        let mut stamped = save_file.clone();
+        if self.stamp_save_date {
+            let stamp_res = stamped.stamp_modified_slots(&save_date_now());
+            self.report_error(stamp_res);
+        }
+        match stamped.write() {
+            Ok(_) => {
+                println!("Save successful!");
+                *save_file = stamped;
+                self.edited_save_file = false;
+            }
+            Err(e) => {
+                println!("ERROR: {}", e);
+                self.error_message = Some(format!("Could not save the file: {}", e));
+            }
+        }
+        if matches!(self.current_view, CurrentMenu::Backups) {
+            self.refresh_backups(save_file);
+        }
+    }
+
+    /// Reads the backups of the open file again.
+    fn refresh_backups(&mut self, save_file: &SaveFile) {
+        self.backup_chosen = None;
+        self.backups = match save_file.path().map(backup_folder) {
+            Some(Ok(folder)) => match list_backups(&folder) {
+                Ok(backups) => backups,
+                Err(e) => {
+                    self.report_error(Err(e));
+                    vec![]
+                }
+            },
+            Some(Err(e)) => {
+                self.report_error(Err(e));
+                vec![]
+            }
+            None => vec![],
+        };
+    }
+
+    fn show_main_menu(&mut self, ctx: &Context, save_file: &mut SaveFile) {
         CentralPanel::default().show(ctx, |ui| {
-            let available_space = ui.available_size();
-            ui.set_min_size(available_space);
+            ui.set_min_size(ui.available_size());
+
+            match save_file.path() {
+                Some(path) => ui.label(format!("File: {}", path.display())),
+                None => ui.label("No file open"),
+            };
+
+            ui.horizontal(|ui| {
+                ui.label("Global Data");
+                // nothing in it is confirmed yet, so it can't be edited
+                if ui.button("See details").clicked() {
+                    self.global_data_chosen = true;
+                    self.current_view = CurrentMenu::FileDetails;
+                    self.edit_mode = false;
+                }
+            });
 
+            let available_space = ui.available_size();
             egui::Grid::new("FileSelectGrid")
                 .min_col_width(available_space.x / 2.)
                 .min_row_height(available_space.y / 2.)
@@ -241,29 +857,34 @@ impl App {
                 .show(ui, |ui| {
                     ui.vertical(|ui| {
                         ui.label("Save 1");
-                        self.generate_main_menu_table(ui, 1);
+                        self.generate_main_menu_table(ui, 1, save_file);
                     });
                     ui.vertical(|ui| {
                         ui.label("Save 2");
-                        self.generate_main_menu_table(ui, 2);
+                        self.generate_main_menu_table(ui, 2, save_file);
                     });
                     ui.end_row();
 
                     ui.vertical(|ui| {
                         ui.label("Save 3");
-                        self.generate_main_menu_table(ui, 3);
+                        self.generate_main_menu_table(ui, 3, save_file);
                     });
                     ui.vertical(|ui| {
                         ui.label("Save 4");
-                        self.generate_main_menu_table(ui, 4);
+                        self.generate_main_menu_table(ui, 4, save_file);
                     });
                 });
-            self.proceed_confirm_reload(ui);
+            self.proceed_confirm_reload(ui, save_file);
             self.confirm_close_without_save(ctx, ui);
         });
     }
 
-    fn generate_main_menu_table(&mut self, ui: &mut eframe::egui::Ui, save_slot: u8) {
+    fn generate_main_menu_table(
+        &mut self,
+        ui: &mut eframe::egui::Ui,
+        save_slot: u8,
+        save_file: &mut SaveFile,
+    ) {
         let id = match save_slot {
             1 => "Save Slot 1",
             2 => "Save Slot 2",
@@ -275,77 +896,31 @@ impl App {
             }
         };
 
-        let mut save_data_guard = SAVE_DATA.lock().unwrap();
-
-        let file_exists_data = get_save_file_variable(SaveDataVar::FileExists, save_slot);
-        let file_exists = get_int_value_from_save_data(
-            save_data_guard.to_vec(),
-            file_exists_data.slot_base_add,
-            file_exists_data.offset,
-            &file_exists_data.int_type,
-        );
-
-        if file_exists == 0 {
-            if ui.button("Create Save File").clicked() {
-                self.edited_save_file = true;
-                let new_save_file = get_new_save_file();
-                let start_add = get_save_slot_base_add(save_slot);
+        let slot_data = save_file.slot(save_slot);
 
-                for (i, new_save_byte) in new_save_file.iter().enumerate() {
-                    save_data_guard[start_add as usize + i] = *new_save_byte;
+        match slot_data.exists() {
+            Ok(true) => {}
+            Ok(false) => {
+                if ui.button("Create Save File").clicked() {
+                    self.edited_save_file = true;
+                    let create_res = save_file.slot_mut(save_slot).create();
+                    self.report_error(create_res);
                 }
+                return;
+            }
+            Err(e) => {
+                ui.label(format!("Error: {}", e));
+                return;
             }
-            return;
         }
 
-        let lives_data = get_save_file_variable(SaveDataVar::Lives, save_slot);
-        let lives = get_int_value_from_save_data(
-            save_data_guard.to_vec(),
-            lives_data.slot_base_add,
-            lives_data.offset,
-            &lives_data.int_type,
-        );
-
-        let hours_data = get_save_file_variable(SaveDataVar::PlayTimeHours, save_slot);
-        let minutes_data = get_save_file_variable(SaveDataVar::PlayTimeMinutes, save_slot);
-        let sec_data = get_save_file_variable(SaveDataVar::PlayTimeSeconds, save_slot);
-        let hours = get_int_value_from_save_data(
-            save_data_guard.to_vec(),
-            hours_data.slot_base_add,
-            hours_data.offset,
-            &hours_data.int_type,
-        );
-        let minutes = get_int_value_from_save_data(
-            save_data_guard.to_vec(),
-            minutes_data.slot_base_add,
-            minutes_data.offset,
-            &minutes_data.int_type,
-        );
-        let seconds = get_int_value_from_save_data(
-            save_data_guard.to_vec(),
-            sec_data.slot_base_add,
-            sec_data.offset,
-            &sec_data.int_type,
-        );
-
-        let hours_str = if hours <= 9 {
-            format!("00{}", hours)
-        } else if hours <= 99 {
-            format!("0{}", hours)
-        } else {
-            hours.to_string()
-        };
-        let min_str = if minutes <= 9 {
-            format!("0{}", minutes)
-        } else {
-            minutes.to_string()
-        };
-        let sec_str = if seconds <= 9 {
-            format!("0{}", seconds)
-        } else {
-            seconds.to_string()
+        let (lives, final_time) = match read_slot_summary(slot_data) {
+            Ok(summary) => summary,
+            Err(e) => {
+                ui.label(format!("Error: {}", e));
+                return;
+            }
         };
-        let final_time = format!("{}:{}:{}", hours_str, min_str, sec_str);
 
         TableBuilder::new(ui)
             .id_salt(id)
@@ -387,28 +962,24 @@ impl App {
             });
         if ui.button("See details").clicked() {
             self.save_slot_chosen = save_slot;
+            self.global_data_chosen = false;
             self.current_view = CurrentMenu::FileDetails;
             self.edit_mode = false;
         }
         if ui.button("Edit").clicked() {
             self.save_slot_chosen = save_slot;
+            self.global_data_chosen = false;
             self.current_view = CurrentMenu::FileDetails;
             self.edit_mode = true;
         }
         if ui.button("Delete").clicked() {
-            let file_exists_data = get_save_file_variable(SaveDataVar::FileExists, save_slot);
-            modify_save_data(
-                &mut save_data_guard,
-                file_exists_data.slot_base_add,
-                file_exists_data.offset,
-                file_exists_data.int_type,
-                0,
-            );
+            let delete_res = save_file.slot_mut(save_slot).delete();
+            self.report_error(delete_res);
             self.edited_save_file = true;
         }
     }
 
-    fn show_details_save_file(&mut self, ctx: &Context) {
+    fn show_layout_report(&mut self, ctx: &Context, save_file: &mut SaveFile) {
         CentralPanel::default().show(ctx, |ui| {
             let available_space = ui.available_size();
             ui.set_min_size(available_space);
@@ -419,19 +990,21 @@ impl App {
                 };
             });
 
-            let mut extra_columns = 1;
-            if self.show_addresses {
-                extra_columns += 1;
-            }
-            if self.show_save_code_variables {
-                extra_columns += 1;
+            let issues = verify_layout();
+            if issues.is_empty() {
+                ui.label("No overlapping or misplaced variables found.");
+            } else {
+                ui.label(format!(
+                    "{} issues found in the save data layout.",
+                    issues.len()
+                ));
             }
 
-            let table = TableBuilder::new(ui)
-                .id_salt("table_details")
+            TableBuilder::new(ui)
+                .id_salt("table_layout_report")
                 .striped(true)
                 .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
-                .columns(Column::auto(), extra_columns)
+                .column(Column::auto())
                 .column(
                     Column::remainder()
                         .at_least(40.0)
@@ -443,91 +1016,645 @@ impl App {
                         ui.strong("Name");
                         ui.set_width(50.);
                     });
-                    if self.show_save_code_variables {
-                        header.col(|ui| {
-                            ui.strong("Name in Code");
-                            ui.set_width(50.);
-                        });
-                    }
-                    if self.show_addresses {
-                        header.col(|ui| {
-                            ui.strong("Address");
-                            ui.set_width(50.);
-                        });
-                    }
                     header.col(|ui| {
-                        ui.strong("Value");
+                        ui.strong("Issue");
                     });
-                });
-
-            let table_vars = if self.show_simple_data_only {
-                get_basic_save_file_vars(self.save_slot_chosen)
-            } else {
-                get_all_save_file_vars(self.save_slot_chosen)
-            };
-            let mut save_data_guard = SAVE_DATA.lock().unwrap();
-
+                })
+                .body(|mut body| {
+                    for issue in issues {
+                        body.row(30.0, |mut row| {
+                            row.col(|ui| {
+                                ui.label(issue.var.field().variable_name_simple);
+                            });
+                            row.col(|ui| {
+                                ui.label(issue.to_string());
+                            });
+                        });
+                    }
+                });
+            self.proceed_confirm_reload(ui, save_file);
+            self.confirm_close_without_save(ctx, ui);
+        });
+    }
+
+    /// Picks a backup in the backups view and finds what's different from the open file.
+    fn compare_backup(&mut self, i: usize, save_file: &SaveFile) {
+        match self.backups[i].save_file() {
+            Ok(backup_file) => {
+                let diff = diff_save_files(backup_file.as_bytes(), save_file.as_bytes());
+                self.backup_chosen = Some((i, diff));
+            }
+            Err(e) => self.report_error(Err(e)),
+        }
+    }
+
+    /// Backups of the open file, any of them can be compared with it and restored whole or one
+    /// slot at a time. Restoring only changes the open file, it still has to be saved.
+    fn show_backups(&mut self, ctx: &Context, save_file: &mut SaveFile) {
+        CentralPanel::default().show(ctx, |ui| {
+            let available_space = ui.available_size();
+            ui.set_min_size(available_space);
+
+            ui.horizontal(|ui| {
+                if ui.button("Go Back").clicked() {
+                    self.current_view = CurrentMenu::Main;
+                };
+                ui.label("Backups Kept");
+                let kept_response = ui.add(egui::DragValue::new(&mut self.backups_kept));
+                if kept_response.changed() {
+                    self.backups_kept = self.backups_kept.min(1000);
+                }
+                if ui
+                    .button("Delete Older Backups")
+                    .on_hover_text("Backups past the count kept are deleted on the next save too")
+                    .clicked()
+                    && let Some(path) = save_file.path()
+                {
+                    let res = backup_folder(path)
+                        .and_then(|folder| prune_backups(&folder, self.backups_kept));
+                    self.report_error(res);
+                    self.refresh_backups(save_file);
+                }
+                if ui.button("Refresh").clicked() {
+                    self.refresh_backups(save_file);
+                }
+            });
+
+            match save_file.path().map(backup_folder) {
+                Some(Ok(folder)) => {
+                    ui.label(format!("Folder: {}", folder.display()));
+                }
+                Some(Err(e)) => {
+                    ui.label(format!("Error: {}", e));
+                }
+                None => {
+                    ui.label("No file open, there are no backups to show.");
+                    return;
+                }
+            };
+            if self.backups_kept == 0 {
+                ui.label("Backups are turned off, the file is saved without taking one.");
+            }
+            if self.backups.is_empty() {
+                ui.label("No backups yet, one is taken every time the file is saved.");
+                return;
+            }
+
+            ui.separator();
+
+            let mut compare_clicked = None;
+            egui::ScrollArea::vertical()
+                .id_salt("backups_list")
+                .max_height(available_space.y / 2.)
+                .show(ui, |ui| {
+                    egui::Grid::new("backups_grid")
+                        .striped(true)
+                        .num_columns(3)
+                        .show(ui, |ui| {
+                            for (i, backup) in self.backups.iter().enumerate() {
+                                let chosen = self
+                                    .backup_chosen
+                                    .as_ref()
+                                    .is_some_and(|(chosen, _)| *chosen == i);
+                                if ui
+                                    .selectable_label(
+                                        chosen,
+                                        backup
+                                            .date
+                                            .with_timezone(&Local)
+                                            .format("%Y/%m/%d %H:%M:%S")
+                                            .to_string(),
+                                    )
+                                    .clicked()
+                                {
+                                    compare_clicked = Some(i);
+                                }
+                                ui.label(
+                                    backup
+                                        .summary
+                                        .iter()
+                                        .filter(|line| line.starts_with("Save "))
+                                        .cloned()
+                                        .collect::<Vec<String>>()
+                                        .join("\n"),
+                                );
+                                ui.end_row();
+                            }
+                        });
+                });
+            if let Some(i) = compare_clicked {
+                self.compare_backup(i, save_file);
+            }
+
+            let Some((chosen, diff)) = &self.backup_chosen else {
+                ui.label("Pick a backup to compare it with the open file.");
+                return;
+            };
+            let chosen = *chosen;
+            let backup = &self.backups[chosen];
+
+            ui.separator();
+
+            ui.heading(format!(
+                "Backup of {}",
+                backup
+                    .date
+                    .with_timezone(&Local)
+                    .format("%Y/%m/%d %H:%M:%S")
+            ));
+            if let Some(source) = backup.summary.first() {
+                ui.label(source);
+            }
+
+            let mut restore: Option<Option<u8>> = None;
+            ui.horizontal(|ui| {
+                if ui.button("Restore Whole File").clicked() {
+                    restore = Some(None);
+                }
+                for slot in 1..=4 {
+                    if ui.button(format!("Restore Save {}", slot)).clicked() {
+                        restore = Some(Some(slot));
+                    }
+                }
+            });
+
+            if diff.is_empty() {
+                ui.label("The backup is the same as the open file.");
+            }
+            egui::ScrollArea::vertical()
+                .id_salt("backup_diff")
+                .show(ui, |ui| {
+                    for section_diff in diff {
+                        ui.strong(section_diff.section.name());
+                        let mut changes: Vec<String> = section_diff
+                            .vars
+                            .iter()
+                            .map(|var| var.field().variable_name_simple.to_string())
+                            .collect();
+                        if section_diff.unknown_bytes > 0 {
+                            changes.push(format!("{} unknown bytes", section_diff.unknown_bytes));
+                        }
+                        ui.label(changes.join(", "));
+                    }
+                });
+
+            if let Some(slot) = restore {
+                let res = self.backups[chosen]
+                    .save_file()
+                    .and_then(|backup_file| match slot {
+                        Some(slot) => save_file.restore_slot(&backup_file, slot),
+                        None => save_file.restore(backup_file.as_bytes()),
+                    });
+                if res.is_ok() {
+                    self.edited_save_file = true;
+                    self.compare_backup(chosen, save_file);
+                }
+                self.report_error(res);
+            }
+        });
+    }
+
+    /// Which bytes of a slot are described by a variable, the unknown ones that aren't 0 are
+    /// highlighted since they're the ones worth looking into.
+    fn show_coverage_map(&mut self, ctx: &Context, save_file: &mut SaveFile) {
+        CentralPanel::default().show(ctx, |ui| {
+            let available_space = ui.available_size();
+            ui.set_min_size(available_space);
+
+            ui.horizontal(|ui| {
+                if ui.button("Go Back").clicked() {
+                    self.current_view = CurrentMenu::Main;
+                };
+                egui::ComboBox::from_label("Slot")
+                    .selected_text(format!("Save {}", self.save_slot_chosen))
+                    .show_ui(ui, |ui| {
+                        for slot in 1..=4 {
+                            ui.selectable_value(
+                                &mut self.save_slot_chosen,
+                                slot,
+                                format!("Save {}", slot),
+                            );
+                        }
+                    });
+                ui.checkbox(&mut self.show_unknown_ranges_only, "Unknown Only");
+            });
+
+            let (slot_bytes, slot_base_add) = match (
+                save_file.slot(self.save_slot_chosen).bytes(),
+                save_data_info::get_save_slot_base_add(self.save_slot_chosen),
+            ) {
+                (Ok(slot_bytes), Ok(slot_base_add)) => (slot_bytes, slot_base_add),
+                (Err(e), _) | (_, Err(e)) => {
+                    ui.label(format!("Error: {}", e));
+                    return;
+                }
+            };
+            let mut ranges = slot_coverage();
+            let known_bytes: u32 = ranges
+                .iter()
+                .filter(|range| range.is_known())
+                .map(|range| range.end - range.start)
+                .sum();
+            if self.show_unknown_ranges_only {
+                ranges.retain(|range| !range.is_known());
+            }
+            ui.label(format!(
+                "{} of {} bytes of the slot are described ({:.1}%).",
+                known_bytes,
+                SAVE_SLOT_SIZE,
+                known_bytes as f32 * 100. / SAVE_SLOT_SIZE as f32
+            ));
+
+            let table = TableBuilder::new(ui)
+                .id_salt("table_coverage_map")
+                .striped(true)
+                .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                .column(Column::auto())
+                .column(Column::auto())
+                .column(
+                    Column::remainder()
+                        .at_least(40.0)
+                        .clip(true)
+                        .resizable(true),
+                )
+                .header(20.0, |mut header| {
+                    header.col(|ui| {
+                        ui.strong(if self.show_addresses {
+                            "Address"
+                        } else {
+                            "Offset"
+                        });
+                        ui.set_width(50.);
+                    });
+                    header.col(|ui| {
+                        ui.strong("Size");
+                    });
+                    header.col(|ui| {
+                        ui.strong("Described By");
+                    });
+                });
+
+            table.body(|body| {
+                body.rows(30.0, ranges.len(), |mut row| {
+                    let range = &ranges[row.index()];
+                    let base = if self.show_addresses {
+                        slot_base_add
+                    } else {
+                        0
+                    };
+                    row.col(|ui| {
+                        ui.label(format!("{:X}..{:X}", base + range.start, base + range.end));
+                    });
+                    row.col(|ui| {
+                        ui.label(format!("{:X}", range.end - range.start));
+                    });
+                    row.col(|ui| {
+                        if range.is_known() {
+                            let names: Vec<&str> = range
+                                .vars
+                                .iter()
+                                .map(|var| var.field().variable_name_simple)
+                                .collect();
+                            ui.label(names.join(", "));
+                            return;
+                        }
+                        let non_zero = range.non_zero_bytes(slot_bytes);
+                        if non_zero.is_empty() {
+                            ui.label("Unknown, all 0");
+                        } else {
+                            let first: Vec<String> = non_zero
+                                .iter()
+                                .take(8)
+                                .map(|offset| format!("{:X}", base + offset))
+                                .collect();
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "Unknown, {} bytes not 0 at {}{}",
+                                    non_zero.len(),
+                                    first.join(" "),
+                                    if non_zero.len() > first.len() {
+                                        "..."
+                                    } else {
+                                        ""
+                                    }
+                                ))
+                                .color(ui.visuals().warn_fg_color),
+                            );
+                        }
+                    });
+                });
+            });
+            self.proceed_confirm_reload(ui, save_file);
+            self.confirm_close_without_save(ctx, ui);
+        });
+    }
+
+    /// Hex view of the bytes after the last slot, the bytes that changed since the file was
+    /// loaded are highlighted.
+    fn show_system_area(&mut self, ctx: &Context, save_file: &mut SaveFile) {
+        const BYTES_PER_ROW: usize = 16;
+
+        CentralPanel::default().show(ctx, |ui| {
+            let available_space = ui.available_size();
+            ui.set_min_size(available_space);
+
+            let changes = save_file.system_area_changes();
+
+            ui.horizontal(|ui| {
+                if ui.button("Go Back").clicked() {
+                    self.current_view = CurrentMenu::Main;
+                };
+                ui.checkbox(&mut self.show_changed_system_rows_only, "Changed Rows Only");
+                if !changes.is_empty() && ui.button("Revert Changes").clicked() {
+                    save_file.revert_system_area();
+                }
+            });
+            ui.label(format!(
+                "{} bytes after the last slot, {} changed since the file was loaded.",
+                save_file.system_area().len(),
+                changes.len()
+            ));
+
+            let row_indexes: Vec<usize> = if self.show_changed_system_rows_only {
+                let mut rows: Vec<usize> = changes.iter().map(|i| i / BYTES_PER_ROW).collect();
+                rows.dedup();
+                rows
+            } else {
+                (0..save_file.system_area().len().div_ceil(BYTES_PER_ROW)).collect()
+            };
+
+            let table = TableBuilder::new(ui)
+                .id_salt("table_system_area")
+                .striped(true)
+                .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                .column(Column::auto())
+                .column(Column::auto())
+                .column(
+                    Column::remainder()
+                        .at_least(40.0)
+                        .clip(true)
+                        .resizable(true),
+                )
+                .header(20.0, |mut header| {
+                    header.col(|ui| {
+                        ui.strong("Address");
+                        ui.set_width(50.);
+                    });
+                    header.col(|ui| {
+                        ui.strong("Bytes");
+                    });
+                    header.col(|ui| {
+                        ui.strong("Text");
+                    });
+                });
+
+            table.body(|body| {
+                body.rows(30.0, row_indexes.len(), |mut row| {
+                    let row_i = row_indexes[row.index()];
+                    let start = row_i * BYTES_PER_ROW;
+                    let end = (start + BYTES_PER_ROW).min(save_file.system_area().len());
+
+                    row.col(|ui| {
+                        ui.label(format!("{:X}", SYSTEM_AREA_START as usize + start));
+                    });
+                    row.col(|ui| {
+                        if self.edit_mode && self.current_user_input_array_i_selected == Some(row_i)
+                        {
+                            self.show_edit_mode_inputs_system_row(ui, save_file, start, end);
+                            return;
+                        }
+                        ui.spacing_mut().item_spacing.x = 6.;
+                        for (i, byte) in save_file.system_area()[start..end].iter().enumerate() {
+                            let text = egui::RichText::new(format!("{:02X}", byte));
+                            if changes.binary_search(&(start + i)).is_ok() {
+                                ui.label(text.color(ui.visuals().warn_fg_color));
+                            } else {
+                                ui.label(text);
+                            }
+                        }
+                        if self.edit_mode && ui.button("Modify").clicked() {
+                            self.current_user_input_array_i_selected = Some(row_i);
+                            self.current_user_input = save_file.system_area()[start..end]
+                                .iter()
+                                .map(|byte| format!("{:02X}", byte))
+                                .collect::<Vec<String>>()
+                                .join(" ");
+                        }
+                    });
+                    row.col(|ui| {
+                        let text: String = save_file.system_area()[start..end]
+                            .iter()
+                            .map(|byte| {
+                                if byte.is_ascii_graphic() {
+                                    *byte as char
+                                } else {
+                                    '.'
+                                }
+                            })
+                            .collect();
+                        ui.label(text);
+                    });
+                });
+            });
+            self.proceed_confirm_reload(ui, save_file);
+            self.confirm_close_without_save(ctx, ui);
+        });
+    }
+
+    /// Text input for one row of the system area, the bytes are written as hex separated by spaces.
+    fn show_edit_mode_inputs_system_row(
+        &mut self,
+        ui: &mut Ui,
+        save_file: &mut SaveFile,
+        start: usize,
+        end: usize,
+    ) {
+        let input_response = ui.add(
+            egui::TextEdit::singleline(&mut self.current_user_input)
+                .hint_text("Press Enter To End")
+                .desired_width(f32::INFINITY),
+        );
+        if !(input_response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter))) {
+            return;
+        }
+        self.current_user_input_array_i_selected = None;
+
+        let address = SYSTEM_AREA_START as usize + start;
+        let bytes: Result<Vec<u8>, _> = self
+            .current_user_input
+            .split_whitespace()
+            .map(|byte| u8::from_str_radix(byte, 16))
+            .collect();
+        let write_res = match bytes {
+            Ok(bytes) if bytes.len() == end - start => {
+                save_file.as_bytes_mut()[address..address + bytes.len()].copy_from_slice(&bytes);
+                self.edited_save_file = true;
+                Ok(())
+            }
+            Ok(bytes) => Err(SaveError::InvalidValue {
+                address,
+                reason: format!("expected {} bytes, got {}", end - start, bytes.len()),
+            }),
+            Err(e) => Err(SaveError::InvalidValue {
+                address,
+                reason: format!("not a hex byte: {}", e),
+            }),
+        };
+        self.report_error(write_res);
+    }
+
+    fn show_details_save_file(&mut self, ctx: &Context, save_file: &mut SaveFile) {
+        CentralPanel::default().show(ctx, |ui| {
+            let available_space = ui.available_size();
+            ui.set_min_size(available_space);
+
+            ui.horizontal(|ui| {
+                if ui.button("Go Back").clicked() {
+                    self.current_view = CurrentMenu::Main;
+                };
+            });
+
+            let simple_data_only = self.show_simple_data_only;
+            let table_vars: Result<Vec<SaveFileData>, SaveError> = if self.global_data_chosen {
+                // the few header variables are all shown, none of them is basic data
+                Ok(SAVE_FILE_HEADER_FIELDS
+                    .iter()
+                    .map(|field| field.at_header())
+                    .collect())
+            } else {
+                let slot = self.save_slot_chosen;
+                let version = save_file.version();
+                SAVE_FIELDS
+                    .iter()
+                    .filter(|field| !simple_data_only || field.basic)
+                    .filter(|field| field.var.min_version() <= version)
+                    .map(|field| field.at_slot(slot))
+                    .collect()
+            };
+            let table_vars = match table_vars {
+                Ok(table_vars) => table_vars,
+                Err(e) => {
+                    ui.label(format!("Error: {}", e));
+                    return;
+                }
+            };
+
+            let mut extra_columns = 1;
+            if self.show_addresses {
+                extra_columns += 1;
+            }
+            if self.show_save_code_variables {
+                extra_columns += 1;
+            }
+
+            let table = TableBuilder::new(ui)
+                .id_salt("table_details")
+                .striped(true)
+                .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                .columns(Column::auto(), extra_columns)
+                .column(
+                    Column::remainder()
+                        .at_least(40.0)
+                        .clip(true)
+                        .resizable(true),
+                )
+                .header(20.0, |mut header| {
+                    header.col(|ui| {
+                        ui.strong("Name");
+                        ui.set_width(50.);
+                    });
+                    if self.show_save_code_variables {
+                        header.col(|ui| {
+                            ui.strong("Name in Code");
+                            ui.set_width(50.);
+                        });
+                    }
+                    if self.show_addresses {
+                        header.col(|ui| {
+                            ui.strong("Address");
+                            ui.set_width(50.);
+                        });
+                    }
+                    header.col(|ui| {
+                        ui.strong("Value");
+                    });
+                });
+
+            let save_data = save_file.as_bytes_mut();
+
             table.body(|mut body| {
                 for var_data in table_vars {
                     let value_str: String = match var_data.int_type {
                         save_data_info::SaveDataIntType::Bool => {
-                            let val_int = get_int_value_from_save_data(
-                                save_data_guard.to_vec(),
+                            match get_int_value_from_save_data(
+                                save_data,
                                 var_data.slot_base_add,
                                 var_data.offset,
                                 &var_data.int_type,
-                            );
-                            let val_bool_str: String;
-                            if val_int == 0 {
-                                val_bool_str = "False".to_string();
-                            } else if val_int == 1 {
-                                val_bool_str = "True".to_string();
-                            } else {
-                                val_bool_str = "Error: Not 0 or 1".to_string();
+                            ) {
+                                Ok(0) => "False".to_string(),
+                                Ok(1) => "True".to_string(),
+                                Ok(_) => "Error: Not 0 or 1".to_string(),
+                                Err(e) => format!("Error: {}", e),
                             }
-                            val_bool_str
                         }
-                        save_data_info::SaveDataIntType::U32 => {
-                            let val_int = get_int_value_from_save_data(
-                                save_data_guard.to_vec(),
+                        save_data_info::SaveDataIntType::U32
+                        | save_data_info::SaveDataIntType::I32 => {
+                            match get_int_value_from_save_data(
+                                save_data,
                                 var_data.slot_base_add,
                                 var_data.offset,
                                 &var_data.int_type,
-                            );
-                            val_int.to_string()
+                            ) {
+                                Ok(val_int) => val_int.to_string(),
+                                Err(e) => format!("Error: {}", e),
+                            }
                         }
-                        save_data_info::SaveDataIntType::I32 => {
-                            let val_int = get_int_value_from_save_data(
-                                save_data_guard.to_vec(),
+                        save_data_info::SaveDataIntType::F32 => {
+                            match get_float_value_from_save_data(
+                                save_data,
                                 var_data.slot_base_add,
                                 var_data.offset,
                                 &var_data.int_type,
-                            );
-                            val_int.to_string()
+                            ) {
+                                Ok(val_float) => format!("{:.3}", val_float),
+                                Err(e) => format!("Error: {}", e),
+                            }
+                        }
+                        save_data_info::SaveDataIntType::Bitfield => {
+                            match get_int_value_from_save_data(
+                                save_data,
+                                var_data.slot_base_add,
+                                var_data.offset,
+                                &var_data.int_type,
+                            ) {
+                                Ok(val_int) => bitfield_to_bits(val_int),
+                                Err(e) => format!("Error: {}", e),
+                            }
                         }
                         save_data_info::SaveDataIntType::Arrayi32(_)
                         | save_data_info::SaveDataIntType::Arrayu8(_)
-                        | save_data_info::SaveDataIntType::SFigureDisplayInfoArray(_) => {
-                            "list".to_string()
-                        }
+                        | save_data_info::SaveDataIntType::SFigureDisplayInfoArray(_)
+                        | save_data_info::SaveDataIntType::BitfieldArray(_) => "list".to_string(),
                         save_data_info::SaveDataIntType::ArrayText(_) => {
                             get_text_value_from_save_data(
-                                save_data_guard.to_vec(),
+                                save_data,
                                 var_data.slot_base_add,
                                 var_data.offset,
                                 &var_data.int_type,
                             )
+                            .unwrap_or_else(|e| format!("Error: {}", e))
                         }
                     };
 
                     body.row(30.0, |mut row| {
                         row.col(|ui| {
-                            ui.label(var_data.variable_name_simple.clone());
+                            ui.label(var_data.variable_name_simple);
                         });
 
                         if self.show_save_code_variables {
                             row.col(|ui| {
-                                ui.label(var_data.variable_name.clone());
+                                ui.label(var_data.variable_name.unwrap_or("(unknown)"));
                             });
                         }
                         if self.show_addresses {
@@ -544,190 +1671,149 @@ impl App {
                                     self.current_user_input_selected = None;
                                 }
                             } else {
-                                match var_data.var {
-                                    SaveDataVar::JukeBoxBGM => {
-                                        if !self.edit_mode || !self.show_combobox_when_possible {
-                                            ui.label(bgm_music_str_to_name(
-                                                value_str.parse().unwrap_or(-100),
-                                            ));
-                                        }
-                                    }
-                                    SaveDataVar::JukeBoxBGMCollab => {
+                                match var_data.var.field().values {
+                                    Some(values) => {
                                         if !self.edit_mode || !self.show_combobox_when_possible {
-                                            ui.label(bgm_music_str_to_name_collab(
-                                                value_str.parse().unwrap_or(-100),
-                                            ));
+                                            ui.label(match value_str.parse() {
+                                                Ok(value) => value_name(value, values),
+                                                Err(_) => value_str,
+                                            });
                                         }
                                     }
-                                    SaveDataVar::PlayerSkinId
-                                    | SaveDataVar::PlayerSkinId2
-                                    | SaveDataVar::PlayerSkinIdCollab => {
-                                        if !self.edit_mode || !self.show_combobox_when_possible {
-                                            ui.label(costume_int_to_name(
-                                                value_str.parse().unwrap_or(-100),
-                                            ));
-                                        }
-                                    }
-                                    _ => {
+                                    None => {
                                         ui.label(value_str);
                                     }
                                 };
 
-                                self.show_edit_mode_inputs_details_table(
-                                    &mut save_data_guard,
-                                    &var_data,
-                                    ui,
-                                );
+                                self.show_edit_mode_inputs_details_table(save_data, &var_data, ui);
                             }
                         });
                     });
                 }
             });
-            self.proceed_confirm_reload(ui);
+            self.proceed_confirm_reload(ui, save_file);
             self.confirm_close_without_save(ctx, ui);
         });
     }
 
     fn show_edit_mode_inputs_details_table(
         &mut self,
-        save_data: &mut Vec<u8>,
+        save_data: &mut [u8],
         var_data: &SaveFileData,
         ui: &mut Ui,
     ) {
-        if !self.edit_mode {
+        if !self.edit_mode || var_data.var.field().read_only {
             return;
         }
 
-        if self.show_combobox_when_possible {
-            if var_data.var == SaveDataVar::JukeBoxBGM {
-                let mut music_picked = get_int_value_from_save_data(
-                    save_data.to_vec(),
-                    var_data.slot_base_add,
-                    var_data.offset,
-                    &var_data.int_type,
-                );
-                let music_picked_before = music_picked;
-                egui::ComboBox::from_label("Pick a Song")
-                    .selected_text(bgm_music_str_to_name(music_picked as i32))
-                    .show_ui(ui, |ui| {
-                        for i in -1..=85 {
-                            let song_name = bgm_music_str_to_name(i);
-                            if !song_name.contains("(Invalid)") {
-                                ui.selectable_value(&mut music_picked, i.into(), song_name);
-                            }
-                        }
-                    });
-                if music_picked != music_picked_before {
-                    modify_save_data(
-                        save_data,
-                        var_data.slot_base_add,
-                        var_data.offset,
-                        var_data.int_type,
-                        music_picked,
-                    );
-                }
-                return;
-            } else if var_data.var == SaveDataVar::JukeBoxBGMCollab {
-                let mut music_picked = get_int_value_from_save_data(
-                    save_data.to_vec(),
-                    var_data.slot_base_add,
-                    var_data.offset,
-                    &var_data.int_type,
-                );
-                let music_picked_before = music_picked;
-                egui::ComboBox::from_label("Pick a Song")
-                    .selected_text(bgm_music_str_to_name_collab(music_picked as i32))
-                    .show_ui(ui, |ui| {
-                        for i in -1..=96 {
-                            let song_name = bgm_music_str_to_name_collab(i);
-                            if !song_name.contains("(Invalid)") {
-                                ui.selectable_value(&mut music_picked, i.into(), song_name);
-                            }
-                        }
-                    });
-                if music_picked != music_picked_before {
-                    modify_save_data(
-                        save_data,
-                        var_data.slot_base_add,
-                        var_data.offset,
-                        var_data.int_type,
-                        music_picked,
-                    );
-                }
+        if self.show_combobox_when_possible
+            && let Some(values) = var_data.var.field().values
+        {
+            let Ok(mut value_picked) = get_int_value_from_save_data(
+                save_data,
+                var_data.slot_base_add,
+                var_data.offset,
+                &var_data.int_type,
+            ) else {
                 return;
-            } else if var_data.var == SaveDataVar::PlayerSkinId
-                || var_data.var == SaveDataVar::PlayerSkinId2
-                || var_data.var == SaveDataVar::PlayerSkinIdCollab
-            {
-                let mut skin_picked = get_int_value_from_save_data(
-                    save_data.to_vec(),
+            };
+            let value_picked_before = value_picked;
+            egui::ComboBox::from_id_salt(var_data.variable_name_simple)
+                .selected_text(value_name(value_picked, values))
+                .show_ui(ui, |ui| {
+                    // a value without a name can still be kept
+                    if !values
+                        .iter()
+                        .any(|(value, _)| *value == value_picked_before)
+                    {
+                        ui.selectable_value(
+                            &mut value_picked,
+                            value_picked_before,
+                            value_name(value_picked_before, values),
+                        );
+                    }
+                    for (value, _) in values {
+                        ui.selectable_value(&mut value_picked, *value, value_name(*value, values));
+                    }
+                });
+            if value_picked != value_picked_before {
+                self.report_error(modify_save_data(
+                    save_data,
                     var_data.slot_base_add,
                     var_data.offset,
-                    &var_data.int_type,
-                );
-                let skin_picked_before = skin_picked;
-                egui::ComboBox::from_label("Pick a Costume")
-                    .selected_text(costume_int_to_name(skin_picked as i32))
-                    .show_ui(ui, |ui| {
-                        if var_data.var == SaveDataVar::PlayerSkinId
-                            || var_data.var == SaveDataVar::PlayerSkinId2
-                        {
-                            for i in -1..=31 {
-                                let skin_name = costume_int_to_name(i);
-
-                                ui.selectable_value(&mut skin_picked, i.into(), skin_name);
-                            }
-                        } else {
-                            let skin_name = costume_int_to_name(-1);
-                            ui.selectable_value(&mut 32, -1, skin_name);
-                            let skin_name = costume_int_to_name(32);
-                            ui.selectable_value(&mut 32, 32, skin_name);
-                        }
-                    });
-                if skin_picked != skin_picked_before {
-                    modify_save_data(
-                        save_data,
-                        var_data.slot_base_add,
-                        var_data.offset,
-                        var_data.int_type,
-                        skin_picked,
-                    );
-                }
+                    var_data.int_type,
+                    value_picked,
+                ));
+                self.edited_save_file = true;
             }
+            return;
         }
 
         match var_data.int_type {
             SaveDataIntType::Bool => {
                 if ui.button("Modify").clicked() {
-                    if get_int_value_from_save_data(
-                        save_data.to_owned(),
+                    let toggle_res = get_int_value_from_save_data(
+                        save_data,
                         var_data.slot_base_add,
                         var_data.offset,
                         &var_data.int_type,
-                    ) > 0
-                    {
+                    )
+                    .and_then(|value| {
                         modify_save_data(
                             save_data,
                             var_data.slot_base_add,
                             var_data.offset,
                             var_data.int_type,
-                            0,
-                        );
-                    } else {
-                        modify_save_data(
-                            save_data,
-                            var_data.slot_base_add,
-                            var_data.offset,
-                            var_data.int_type,
-                            1,
-                        );
+                            if value > 0 { 0 } else { 1 },
+                        )
+                    });
+                    self.report_error(toggle_res);
+                    self.edited_save_file = true;
+                };
+            }
+            SaveDataIntType::U32 => {
+                if self.current_user_input_selected.is_some()
+                    && var_data.var == self.current_user_input_selected.unwrap_or_default()
+                {
+                    let prev_input = self.current_user_input.clone();
+                    let input_response = ui.add(
+                        egui::TextEdit::singleline(&mut self.current_user_input)
+                            .hint_text("Press Enter To End"),
+                    );
+                    if self.current_user_input.is_empty() {
+                        self.current_user_input = "0".into();
+                    }
+
+                    let input_to_num: Result<u32, _> = self.current_user_input.parse();
+                    match input_to_num {
+                        Ok(num) => {
+                            if input_response.lost_focus()
+                                && ui.input(|i| i.key_pressed(Key::Enter))
+                            {
+                                self.report_error(modify_save_data(
+                                    save_data,
+                                    var_data.slot_base_add,
+                                    var_data.offset,
+                                    var_data.int_type,
+                                    num.into(),
+                                ));
+                                self.current_user_input_selected = None;
+                            }
+                            self.edited_save_file = true;
+                        }
+                        Err(_) => {
+                            self.current_user_input = prev_input;
+                        }
                     }
-                    self.edited_save_file = true;
-                };
+                } else if ui.button("Modify").clicked() {
+                    self.current_user_input_selected = Some(var_data.var);
+                    self.current_user_input = "".to_string();
+                }
             }
-            SaveDataIntType::U32 => {
+
+            SaveDataIntType::I32 => {
                 if self.current_user_input_selected.is_some()
-                    && var_data.var == self.current_user_input_selected.clone().unwrap_or_default()
+                    && var_data.var == self.current_user_input_selected.unwrap_or_default()
                 {
                     let prev_input = self.current_user_input.clone();
                     let input_response = ui.add(
@@ -738,19 +1824,23 @@ impl App {
                         self.current_user_input = "0".into();
                     }
 
-                    let input_to_num: Result<u32, _> = self.current_user_input.parse();
+                    let input_to_num: Result<i32, _> = if self.current_user_input == "-" {
+                        Ok(0)
+                    } else {
+                        self.current_user_input.parse()
+                    };
                     match input_to_num {
                         Ok(num) => {
                             if input_response.lost_focus()
                                 && ui.input(|i| i.key_pressed(Key::Enter))
                             {
-                                modify_save_data(
+                                self.report_error(modify_save_data(
                                     save_data,
                                     var_data.slot_base_add,
                                     var_data.offset,
                                     var_data.int_type,
                                     num.into(),
-                                );
+                                ));
                                 self.current_user_input_selected = None;
                             }
                             self.edited_save_file = true;
@@ -760,14 +1850,13 @@ impl App {
                         }
                     }
                 } else if ui.button("Modify").clicked() {
-                    self.current_user_input_selected = Some(var_data.var.clone());
+                    self.current_user_input_selected = Some(var_data.var);
                     self.current_user_input = "".to_string();
                 }
             }
-
-            SaveDataIntType::I32 => {
+            SaveDataIntType::F32 => {
                 if self.current_user_input_selected.is_some()
-                    && var_data.var == self.current_user_input_selected.clone().unwrap_or_default()
+                    && var_data.var == self.current_user_input_selected.unwrap_or_default()
                 {
                     let prev_input = self.current_user_input.clone();
                     let input_response = ui.add(
@@ -778,8 +1867,8 @@ impl App {
                         self.current_user_input = "0".into();
                     }
 
-                    let input_to_num: Result<i32, _> = if self.current_user_input == "-" {
-                        Ok(0)
+                    let input_to_num: Result<f32, _> = if self.current_user_input == "-" {
+                        Ok(0.)
                     } else {
                         self.current_user_input.parse()
                     };
@@ -788,13 +1877,12 @@ impl App {
                             if input_response.lost_focus()
                                 && ui.input(|i| i.key_pressed(Key::Enter))
                             {
-                                modify_save_data(
+                                self.report_error(modify_save_data_float(
                                     save_data,
                                     var_data.slot_base_add,
                                     var_data.offset,
-                                    var_data.int_type,
-                                    num.into(),
-                                );
+                                    num,
+                                ));
                                 self.current_user_input_selected = None;
                             }
                             self.edited_save_file = true;
@@ -804,18 +1892,74 @@ impl App {
                         }
                     }
                 } else if ui.button("Modify").clicked() {
-                    self.current_user_input_selected = Some(var_data.var.clone());
+                    self.current_user_input_selected = Some(var_data.var);
                     self.current_user_input = "".to_string();
                 }
             }
+            SaveDataIntType::ArrayText(_) => {
+                if var_data.var != SaveDataVar::SaveDate {
+                    return;
+                }
+                if self.current_user_input_selected.is_some()
+                    && var_data.var == self.current_user_input_selected.unwrap_or_default()
+                {
+                    let input_response = ui.add(
+                        egui::TextEdit::singleline(&mut self.current_user_input)
+                            .hint_text("YYYY/MM/DD hh:mm"),
+                    );
+                    if input_response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)) {
+                        let address = (var_data.slot_base_add + var_data.offset) as usize;
+                        let write_res = parse_save_date(&self.current_user_input, address)
+                            .and_then(|date| {
+                                modify_save_data_text(
+                                    save_data,
+                                    var_data.slot_base_add,
+                                    var_data.offset,
+                                    var_data.int_type,
+                                    &format_save_date(&date),
+                                )
+                            });
+                        if write_res.is_ok() {
+                            self.edited_save_file = true;
+                        }
+                        self.report_error(write_res);
+                        self.current_user_input_selected = None;
+                    }
+                } else {
+                    if ui.button("Modify").clicked() {
+                        self.current_user_input_selected = Some(var_data.var);
+                        self.current_user_input = "".to_string();
+                    }
+                    if ui.button("Now").clicked() {
+                        self.report_error(modify_save_data_text(
+                            save_data,
+                            var_data.slot_base_add,
+                            var_data.offset,
+                            var_data.int_type,
+                            &format_save_date(&save_date_now()),
+                        ));
+                        self.edited_save_file = true;
+                    }
+                }
+            }
+            SaveDataIntType::Bitfield => {
+                if ui.button("Modify").clicked() {
+                    self.bitfield_editing = Some((*var_data, None));
+                }
+            }
             SaveDataIntType::Arrayi32(_)
-            | SaveDataIntType::ArrayText(_)
             | SaveDataIntType::Arrayu8(_)
-            | SaveDataIntType::SFigureDisplayInfoArray(_) => {}
+            | SaveDataIntType::SFigureDisplayInfoArray(_)
+            | SaveDataIntType::BitfieldArray(_) => {}
         }
     }
 
-    fn show_single_array_table(&mut self, ctx: &Context, var_data: SaveFileData) {
+    fn show_single_array_table(
+        &mut self,
+        ctx: &Context,
+        var_data: SaveFileData,
+        save_file: &mut SaveFile,
+    ) {
         CentralPanel::default().show(ctx, |ui| {
             let available_space = ui.available_size();
             ui.set_min_size(available_space);
@@ -824,11 +1968,12 @@ impl App {
                 if ui.button("Go Back To All Data").clicked() {
                     self.single_save_file_view = SaveFileCurrentView::AllVars;
                     self.current_user_input_array_i_selected = None;
+                    self.current_user_input_figure_selected = None;
                 };
             });
 
             let table = TableBuilder::new(ui)
-                .id_salt(var_data.variable_name.clone())
+                .id_salt(var_data.variable_name_simple)
                 .striped(true)
                 .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
                 .column(Column::auto())
@@ -868,196 +2013,200 @@ impl App {
                     });
                 });
 
-            let mut save_data_guard = SAVE_DATA.lock().unwrap();
+            let save_data = save_file.as_bytes_mut();
 
             match var_data.int_type {
-                SaveDataIntType::Arrayi32(_) | SaveDataIntType::Arrayu8(_) => {
+                SaveDataIntType::Arrayi32(_)
+                | SaveDataIntType::Arrayu8(_)
+                | SaveDataIntType::BitfieldArray(_) => {
                     let vec_for_table = get_int_array_from_save_data(
-                        save_data_guard.to_vec(),
+                        save_data,
                         var_data.slot_base_add,
                         var_data.offset,
                         &var_data.int_type,
                     );
 
                     table.body(|mut body| {
-                        for (i, var) in vec_for_table.iter().enumerate() {
-                            // TODO somehow sort by name in input config view
-                            if self.show_simple_data_only
-                                && (var_data.var == SaveDataVar::KeyConfigP1
-                                    || var_data.var == SaveDataVar::KeyConfigP2)
-                            {
-                                let indexes_with_main_config = [
-                                    72, 73, 75, 74, 456, 458, 509, 519, 503, 77, 81, 122, 48, 49,
-                                    51, 50, 432, 434, 485, 495, 480, 53, 57, 98,
-                                ];
-                                if !indexes_with_main_config.contains(&i) {
-                                    continue;
-                                }
+                        let vec_for_table = match &vec_for_table {
+                            Ok(vec_for_table) => vec_for_table,
+                            Err(e) => {
+                                show_error_row(&mut body, e);
+                                return;
                             }
-
-                            body.row(30.0, |mut row| {
-                                match var_data.var {
-                                    SaveDataVar::ScoreList
-                                    | SaveDataVar::TimeTrialList
-                                    | SaveDataVar::TimeTrialCoopList
-                                    | SaveDataVar::StageFlagList
-                                    | SaveDataVar::StageCherryFlag
-                                    | SaveDataVar::StageStrawberryFlag
-                                    | SaveDataVar::StageOrangeFlag
-                                    | SaveDataVar::StageAppleFlag
-                                    | SaveDataVar::StageMelonFlag
-                                    | SaveDataVar::StageCherryNum
-                                    | SaveDataVar::StageStrawberryNum
-                                    | SaveDataVar::StageOrangeNum
-                                    | SaveDataVar::StageAppleNum
-                                    | SaveDataVar::StageMelonNum => {
-                                        let level_name = int_to_stage_name(i, false);
-                                        row.col(|ui| {
-                                            ui.label(level_name);
-                                        });
-                                    }
-                                    SaveDataVar::StageMazeFlagList
-                                    | SaveDataVar::MazeFlagList
-                                    | SaveDataVar::MazesScoreList => {
-                                        let maze = int_to_maze_name(i);
-                                        row.col(|ui| {
-                                            ui.label(maze);
-                                        });
-                                    }
-                                    SaveDataVar::MissionFlag => {
-                                        let level_name = int_to_mission_level(i);
-                                        row.col(|ui| {
-                                            ui.label(level_name);
-                                        });
-                                    }
-                                    SaveDataVar::MissionRewardFlag => {
-                                        let level_name = int_to_stage_name(i, true);
-                                        row.col(|ui| {
-                                            ui.label(level_name);
-                                        });
-                                    }
-                                    SaveDataVar::KeyConfigP1 | SaveDataVar::KeyConfigP2 => {
-                                        let movement_type = array_index_to_input_type(i);
-                                        row.col(|ui| {
-                                            ui.label(movement_type);
-                                        });
-                                    }
-                                    _ => {
-                                        row.col(|ui| {
-                                            ui.label(i.to_string());
-                                        });
-                                    }
+                        };
+                        // TODO somehow sort by name in input config view
+                        let indexes_with_main_config = [
+                            72, 73, 75, 74, 456, 458, 509, 519, 503, 77, 81, 122, 48, 49, 51, 50,
+                            432, 434, 485, 495, 480, 53, 57, 98,
+                        ];
+                        let only_main_config = self.show_simple_data_only
+                            && (var_data.var == SaveDataVar::KeyConfigP1
+                                || var_data.var == SaveDataVar::KeyConfigP2);
+                        let row_indexes: Vec<usize> = (0..vec_for_table.len())
+                            .filter(|i| !only_main_config || indexes_with_main_config.contains(i))
+                            .collect();
+
+                        // only the rows on screen get built, the input config lists are huge
+                        body.rows(30.0, row_indexes.len(), |mut row| {
+                            let i = row_indexes[row.index()];
+                            let var = &vec_for_table[i];
+                            match var_data.var {
+                                SaveDataVar::ScoreList
+                                | SaveDataVar::TimeTrialList
+                                | SaveDataVar::TimeTrialCoopList
+                                | SaveDataVar::StageFlagList
+                                | SaveDataVar::StageCherryFlag
+                                | SaveDataVar::StageStrawberryFlag
+                                | SaveDataVar::StageOrangeFlag
+                                | SaveDataVar::StageAppleFlag
+                                | SaveDataVar::StageMelonFlag
+                                | SaveDataVar::StageCherryNum
+                                | SaveDataVar::StageStrawberryNum
+                                | SaveDataVar::StageOrangeNum
+                                | SaveDataVar::StageAppleNum
+                                | SaveDataVar::StageMelonNum => {
+                                    let level_name = int_to_stage_name(i, false);
+                                    row.col(|ui| {
+                                        ui.label(level_name);
+                                    });
                                 }
-
-                                if self.show_addresses {
+                                SaveDataVar::StageMazeFlagList
+                                | SaveDataVar::MazeFlagList
+                                | SaveDataVar::MazesScoreList => {
+                                    let maze = int_to_maze_name(i);
                                     row.col(|ui| {
-                                        let bytes_amount: u32 = match var_data.int_type {
-                                            save_data_info::SaveDataIntType::Arrayi32(_) => 4,
-                                            save_data_info::SaveDataIntType::Arrayu8(_) => 1,
-                                            _ => 0, // should never happen
-                                        };
-                                        ui.label(format!(
-                                            "{:X}",
-                                            (var_data.slot_base_add
-                                                + var_data.offset
-                                                + (i as u32 * bytes_amount))
-                                        ));
+                                        ui.label(maze);
+                                    });
+                                }
+                                SaveDataVar::MissionFlag => {
+                                    let level_name = int_to_mission_level(i);
+                                    row.col(|ui| {
+                                        ui.label(level_name);
+                                    });
+                                }
+                                SaveDataVar::MissionRewardFlag => {
+                                    let level_name = int_to_stage_name(i, true);
+                                    row.col(|ui| {
+                                        ui.label(level_name);
+                                    });
+                                }
+                                SaveDataVar::KeyConfigP1 | SaveDataVar::KeyConfigP2 => {
+                                    let movement_type = array_index_to_input_type(i);
+                                    row.col(|ui| {
+                                        ui.label(movement_type);
+                                    });
+                                }
+                                _ => {
+                                    row.col(|ui| {
+                                        ui.label(i.to_string());
                                     });
                                 }
+                            }
+
+                            if self.show_addresses {
                                 row.col(|ui| {
-                                    match var_data.var {
-                                        SaveDataVar::TimeTrialList
-                                        | SaveDataVar::TimeTrialCoopList => {
-                                            let seconds_total = var / 100;
-                                            let ms = var - seconds_total * 100;
-                                            let minutes = seconds_total / 60;
-                                            let seconds = seconds_total % 60;
-
-                                            let ms_str = if ms < 10 {
-                                                format!("0{}", ms)
-                                            } else {
-                                                ms.to_string()
-                                            };
+                                    let bytes_amount: u32 = match var_data.int_type {
+                                        save_data_info::SaveDataIntType::Arrayi32(_)
+                                        | save_data_info::SaveDataIntType::BitfieldArray(_) => 4,
+                                        save_data_info::SaveDataIntType::Arrayu8(_) => 1,
+                                        _ => 0, // should never happen
+                                    };
+                                    ui.label(format!(
+                                        "{:X}",
+                                        (var_data.slot_base_add
+                                            + var_data.offset
+                                            + (i as u32 * bytes_amount))
+                                    ));
+                                });
+                            }
+                            row.col(|ui| {
+                                match var_data.var {
+                                    SaveDataVar::TimeTrialList | SaveDataVar::TimeTrialCoopList => {
+                                        let seconds_total = var / 100;
+                                        let ms = var - seconds_total * 100;
+                                        let minutes = seconds_total / 60;
+                                        let seconds = seconds_total % 60;
+
+                                        let ms_str = if ms < 10 {
+                                            format!("0{}", ms)
+                                        } else {
+                                            ms.to_string()
+                                        };
 
-                                            let seconds_str = if seconds < 10 {
-                                                format!("0{}", seconds)
-                                            } else {
-                                                seconds.to_string()
-                                            };
+                                        let seconds_str = if seconds < 10 {
+                                            format!("0{}", seconds)
+                                        } else {
+                                            seconds.to_string()
+                                        };
 
-                                            ui.label(format!(
-                                                "{}:{}.{}",
-                                                minutes, seconds_str, ms_str
-                                            ));
-                                        }
-                                        SaveDataVar::StageFlagList | SaveDataVar::MazeFlagList => {
-                                            if !self.edit_mode || !self.show_combobox_when_possible
-                                            {
-                                                match var {
-                                                    0 => ui.label("0 Locked"),
-                                                    1 => ui.label("1 Unlocked"),
-                                                    2 => ui.label("2 Entered"),
-                                                    3 => ui.label("3 Complete"),
-                                                    _ => ui.label(var.to_string()),
-                                                };
-                                            }
+                                        ui.label(format!("{}:{}.{}", minutes, seconds_str, ms_str));
+                                    }
+                                    SaveDataVar::StageFlagList | SaveDataVar::MazeFlagList => {
+                                        if !self.edit_mode || !self.show_combobox_when_possible {
+                                            match var {
+                                                0 => ui.label("0 Locked"),
+                                                1 => ui.label("1 Unlocked"),
+                                                2 => ui.label("2 Entered"),
+                                                3 => ui.label("3 Complete"),
+                                                _ => ui.label(var.to_string()),
+                                            };
                                         }
-                                        SaveDataVar::KeyConfigP1 | SaveDataVar::KeyConfigP2 => {
-                                            if !self.edit_mode || !self.show_combobox_when_possible
-                                            {
-                                                let is_controller = array_index_to_input_type(i)
-                                                    .contains("Controller");
-                                                if is_controller {
-                                                    ui.label(int_to_controller_btn(*var));
-                                                } else {
-                                                    ui.label(int_to_key(*var));
-                                                }
+                                    }
+                                    SaveDataVar::KeyConfigP1 | SaveDataVar::KeyConfigP2 => {
+                                        if !self.edit_mode || !self.show_combobox_when_possible {
+                                            let is_controller =
+                                                array_index_to_input_type(i).contains("Controller");
+                                            if is_controller {
+                                                ui.label(int_to_controller_btn(*var));
+                                            } else {
+                                                ui.label(int_to_key(*var));
                                             }
                                         }
+                                    }
 
-                                        SaveDataVar::StageCherryFlag
-                                        | SaveDataVar::StageStrawberryFlag
-                                        | SaveDataVar::StageOrangeFlag
-                                        | SaveDataVar::StageAppleFlag
-                                        | SaveDataVar::StageMelonFlag => {
-                                            ui.label(format!("{} ({:b})", var, var));
+                                    SaveDataVar::StageMazeFlagList => {
+                                        if !self.edit_mode || !self.show_combobox_when_possible {
+                                            match var {
+                                                0 => ui.label("0 Locked"),
+                                                1 => ui.label("1 Unlocked"),
+                                                _ => ui.label(var.to_string()),
+                                            };
                                         }
-                                        SaveDataVar::StageMazeFlagList => {
-                                            if !self.edit_mode || !self.show_combobox_when_possible
-                                            {
-                                                match var {
-                                                    0 => ui.label("0 Locked"),
-                                                    1 => ui.label("1 Unlocked"),
-                                                    _ => ui.label(var.to_string()),
-                                                };
-                                            }
+                                    }
+                                    _ => match var_data.int_type {
+                                        SaveDataIntType::BitfieldArray(_) => {
+                                            ui.label(bitfield_to_bits(*var));
                                         }
                                         _ => {
                                             ui.label(var.to_string());
                                         }
-                                    }
+                                    },
+                                }
 
-                                    self.show_edit_mode_inputs_array_int(
-                                        &mut save_data_guard,
-                                        &var_data.clone(),
-                                        ui,
-                                        i,
-                                    );
-                                });
+                                self.show_edit_mode_inputs_array_int(
+                                    save_data, &var_data, ui, i, *var,
+                                );
                             });
-                        }
+                        });
                     });
                 }
 
                 SaveDataIntType::SFigureDisplayInfoArray(array_len) => {
                     let vec_for_table = get_figure_info_from_save_data(
-                        save_data_guard.to_vec(),
+                        save_data,
                         var_data.slot_base_add,
                         var_data.offset,
                         array_len,
                     );
 
                     table.body(|mut body| {
+                        let vec_for_table = match &vec_for_table {
+                            Ok(vec_for_table) => vec_for_table,
+                            Err(e) => {
+                                show_error_row(&mut body, e);
+                                return;
+                            }
+                        };
                         for (i, figure_info) in vec_for_table.iter().enumerate() {
                             let bytes_amount: u32 = 8;
 
@@ -1080,11 +2229,8 @@ impl App {
                                     ui.label(figure_info.figure_id.to_string());
 
                                     if self.edit_mode {
-                                        if self.current_user_input_array_i_selected.is_some()
-                                            && i * 2
-                                                == self
-                                                    .current_user_input_array_i_selected
-                                                    .unwrap_or_default()
+                                        if self.current_user_input_figure_selected
+                                            == Some((i, FigureField::Id))
                                         {
                                             let prev_input = self.current_user_input.clone();
                                             let input_response = ui.add(
@@ -1108,15 +2254,18 @@ impl App {
                                                     if input_response.lost_focus()
                                                         && ui.input(|i| i.key_pressed(Key::Enter))
                                                     {
-                                                        modify_save_data(
-                                                            &mut save_data_guard,
+                                                        self.report_error(modify_figure_info(
+                                                            save_data,
                                                             var_data.slot_base_add,
-                                                            var_data.offset
-                                                                + (i as u32 * bytes_amount),
+                                                            var_data.offset,
                                                             var_data.int_type,
-                                                            num.into(),
-                                                        );
-                                                        self.current_user_input_array_i_selected =
+                                                            i,
+                                                            SFigureDisplayInfo {
+                                                                figure_id: num,
+                                                                ..*figure_info
+                                                            },
+                                                        ));
+                                                        self.current_user_input_figure_selected =
                                                             None;
                                                     }
                                                     self.edited_save_file = true;
@@ -1126,7 +2275,8 @@ impl App {
                                                 }
                                             }
                                         } else if ui.button("Modify").clicked() {
-                                            self.current_user_input_array_i_selected = Some(i * 2);
+                                            self.current_user_input_figure_selected =
+                                                Some((i, FigureField::Id));
                                             self.current_user_input = "".to_string();
                                         }
                                     }
@@ -1152,11 +2302,11 @@ impl App {
                                     ui.label(format!("{:.1}", figure_info.angle));
 
                                     self.show_edit_mode_inputs_array_sfigure(
-                                        &mut save_data_guard,
-                                        &var_data.clone(),
+                                        save_data,
+                                        &var_data,
                                         ui,
                                         i,
-                                        bytes_amount,
+                                        *figure_info,
                                     );
                                 });
                             });
@@ -1166,11 +2316,13 @@ impl App {
                 SaveDataIntType::Bool
                 | SaveDataIntType::U32
                 | SaveDataIntType::I32
-                | SaveDataIntType::ArrayText(_) => {
+                | SaveDataIntType::F32
+                | SaveDataIntType::ArrayText(_)
+                | SaveDataIntType::Bitfield => {
                     println!("Not an array!");
                 }
             }
-            self.proceed_confirm_reload(ui);
+            self.proceed_confirm_reload(ui, save_file);
             self.confirm_close_without_save(ctx, ui);
         });
     }
@@ -1181,14 +2333,15 @@ impl App {
         var_data: &SaveFileData,
         ui: &mut Ui,
         array_index: usize,
+        current_value: i64,
     ) {
-        if !self.edit_mode {
+        if !self.edit_mode || var_data.var.field().read_only {
             return;
         }
 
         if self.show_combobox_when_possible {
             let byte_size = match var_data.int_type {
-                SaveDataIntType::Arrayi32(_) => 4,
+                SaveDataIntType::Arrayi32(_) | SaveDataIntType::BitfieldArray(_) => 4,
                 SaveDataIntType::Arrayu8(_) => 1,
                 // these should never happen
                 _ => {
@@ -1199,14 +2352,7 @@ impl App {
 
             if var_data.var == SaveDataVar::KeyConfigP1 || var_data.var == SaveDataVar::KeyConfigP2
             {
-                let mut input_config_data = *get_int_array_from_save_data(
-                    save_data_guard.to_vec(),
-                    var_data.slot_base_add,
-                    var_data.offset,
-                    &var_data.int_type,
-                )
-                .get(array_index)
-                .unwrap_or(&-100);
+                let mut input_config_data = current_value;
                 let config_before = input_config_data;
 
                 let is_controller = array_index_to_input_type(array_index).contains("Controller");
@@ -1222,13 +2368,13 @@ impl App {
                             }
                         });
                     if input_config_data != config_before {
-                        modify_save_data(
+                        self.report_error(modify_save_data(
                             save_data_guard,
                             var_data.slot_base_add,
                             var_data.offset + (array_index as u32 * byte_size),
                             var_data.int_type,
                             input_config_data,
-                        );
+                        ));
                     }
                 } else {
                     egui::ComboBox::from_label("Pick a Key")
@@ -1242,13 +2388,13 @@ impl App {
                             }
                         });
                     if input_config_data != config_before {
-                        modify_save_data(
+                        self.report_error(modify_save_data(
                             save_data_guard,
                             var_data.slot_base_add,
                             var_data.offset + (array_index as u32 * byte_size),
                             var_data.int_type,
                             input_config_data,
-                        );
+                        ));
                     }
                 }
                 return;
@@ -1256,14 +2402,7 @@ impl App {
                 || var_data.var == SaveDataVar::MazeFlagList
                 || var_data.var == SaveDataVar::StageMazeFlagList
             {
-                let mut complete_state = *get_int_array_from_save_data(
-                    save_data_guard.to_vec(),
-                    var_data.slot_base_add,
-                    var_data.offset,
-                    &var_data.int_type,
-                )
-                .get(array_index)
-                .unwrap_or(&-100);
+                let mut complete_state = current_value;
                 let complete_state_before = complete_state;
 
                 let text = match complete_state {
@@ -1286,13 +2425,13 @@ impl App {
                     });
 
                 if complete_state != complete_state_before {
-                    modify_save_data(
+                    self.report_error(modify_save_data(
                         save_data_guard,
                         var_data.slot_base_add,
                         var_data.offset + (array_index as u32 * byte_size),
                         var_data.int_type,
                         complete_state,
-                    );
+                    ));
                 }
                 return;
             }
@@ -1322,13 +2461,13 @@ impl App {
                             if input_response.lost_focus()
                                 && ui.input(|i| i.key_pressed(Key::Enter))
                             {
-                                modify_save_data(
+                                self.report_error(modify_save_data(
                                     save_data_guard,
                                     var_data.slot_base_add,
                                     var_data.offset + (array_index as u32 * 4),
                                     var_data.int_type,
                                     num.into(),
-                                );
+                                ));
                                 self.current_user_input_array_i_selected = None;
                             }
                             self.edited_save_file = true;
@@ -1365,13 +2504,13 @@ impl App {
                             if input_response.lost_focus()
                                 && ui.input(|i| i.key_pressed(Key::Enter))
                             {
-                                modify_save_data(
+                                self.report_error(modify_save_data(
                                     save_data_guard,
                                     var_data.slot_base_add,
                                     var_data.offset + (array_index as u32),
                                     var_data.int_type,
                                     num.into(),
-                                );
+                                ));
                                 self.current_user_input_array_i_selected = None;
                             }
                             self.edited_save_file = true;
@@ -1385,13 +2524,20 @@ impl App {
                     self.current_user_input = "".to_string();
                 }
             }
+            SaveDataIntType::BitfieldArray(_) => {
+                if ui.button("Modify").clicked() {
+                    self.bitfield_editing = Some((*var_data, Some(array_index)));
+                }
+            }
             SaveDataIntType::ArrayText(_) => {
                 // ok this doesn't seem worth it, it's pointless text
             }
             SaveDataIntType::Bool
             | SaveDataIntType::U32
             | SaveDataIntType::I32
-            | SaveDataIntType::SFigureDisplayInfoArray(_) => {}
+            | SaveDataIntType::F32
+            | SaveDataIntType::SFigureDisplayInfoArray(_)
+            | SaveDataIntType::Bitfield => {}
         }
     }
 
@@ -1401,15 +2547,13 @@ impl App {
         var_data: &SaveFileData,
         ui: &mut Ui,
         i: usize,
-        data_size: u32,
+        figure_info: SFigureDisplayInfo,
     ) {
         if !self.edit_mode {
             return;
         }
 
-        if self.current_user_input_array_i_selected.is_some()
-            && (i * 2 + 1) == self.current_user_input_array_i_selected.unwrap_or_default()
-        {
+        if self.current_user_input_figure_selected == Some((i, FigureField::Angle)) {
             let prev_input = self.current_user_input.clone();
             let input_response = ui.add(
                 egui::TextEdit::singleline(&mut self.current_user_input)
@@ -1427,13 +2571,18 @@ impl App {
             match input_to_num {
                 Ok(num) => {
                     if input_response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)) {
-                        modify_save_data_float(
+                        self.report_error(modify_figure_info(
                             save_data_guard,
                             var_data.slot_base_add,
-                            var_data.offset + (i as u32 * data_size + 4),
-                            num,
-                        );
-                        self.current_user_input_array_i_selected = None;
+                            var_data.offset,
+                            var_data.int_type,
+                            i,
+                            SFigureDisplayInfo {
+                                angle: num,
+                                ..figure_info
+                            },
+                        ));
+                        self.current_user_input_figure_selected = None;
                     }
                     self.edited_save_file = true;
                 }
@@ -1442,7 +2591,7 @@ impl App {
                 }
             }
         } else if ui.button("Modify").clicked() {
-            self.current_user_input_array_i_selected = Some(i * 2 + 1);
+            self.current_user_input_figure_selected = Some((i, FigureField::Angle));
             self.current_user_input = "".to_string();
         }
     }
@@ -1485,7 +2634,7 @@ impl App {
         };
     }
 
-    fn proceed_confirm_reload(&mut self, ui: &mut Ui) {
+    fn proceed_confirm_reload(&mut self, ui: &mut Ui, save_file: &mut SaveFile) {
         if !self.show_confirm_reload_modal {
             return;
         }
@@ -1504,8 +2653,9 @@ impl App {
                     |_ui| {},
                     |ui| {
                         if ui.button("Reload").clicked() {
-                            load_save_file();
-                            self.edited_save_file = false;
+                            if self.load_save_file(save_file) {
+                                self.edited_save_file = false;
+                            }
                             ui.close();
                         }
                         if ui.button("Cancel").clicked() {
@@ -1519,6 +2669,233 @@ impl App {
 
         if modal.should_close() {
             self.show_confirm_reload_modal = false;
+            self.pending_save_path = None;
+        }
+    }
+
+    /// Asks for the path of any save file to open, like a copy from another computer.
+    fn show_open_file_modal(&mut self, ctx: &Context, save_file: &mut SaveFile) {
+        if !self.show_open_modal {
+            return;
+        }
+
+        let mut open_clicked = false;
+        let modal = eframe::egui::Modal::new(eframe::egui::Id::new("Open File")).show(ctx, |ui| {
+            ui.set_width(600.0);
+
+            ui.heading("Open Save File");
+
+            ui.separator();
+
+            let input_response = ui.add(
+                egui::TextEdit::singleline(&mut self.open_path_input)
+                    .hint_text("Path to a DAT00000.dat file")
+                    .desired_width(f32::INFINITY),
+            );
+            if input_response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)) {
+                open_clicked = true;
+            }
+
+            egui::Sides::new().show(
+                ui,
+                |_ui| {},
+                |ui| {
+                    if ui.button("Open").clicked() {
+                        open_clicked = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        ui.close();
+                    }
+                },
+            );
+        });
+
+        if open_clicked && !self.open_path_input.trim().is_empty() {
+            self.show_open_modal = false;
+            let path = PathBuf::from(self.open_path_input.trim());
+            self.open_save_location(save_file, path);
+        } else if modal.should_close() {
+            self.show_open_modal = false;
+        }
+    }
+
+    /// Lists every save file found, with the account it belongs to and its slots.
+    fn show_save_picker(&mut self, ctx: &Context, save_file: &mut SaveFile) {
+        if !self.show_save_picker_modal {
+            return;
+        }
+
+        let mut picked = None;
+        let modal =
+            eframe::egui::Modal::new(eframe::egui::Id::new("Pick Save File")).show(ctx, |ui| {
+                ui.set_width(700.0);
+
+                ui.heading("Pick Save File");
+                ui.label("Saves of more than one account were found, pick the one to edit.");
+
+                ui.separator();
+
+                egui::Grid::new("save_picker_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("Steam");
+                        ui.strong("Account");
+                        ui.strong("Slots");
+                        ui.end_row();
+                        for (i, found) in self.save_locations.iter().enumerate() {
+                            ui.label(&found.location.source);
+                            ui.label(&found.location.account)
+                                .on_hover_text(found.location.path.display().to_string());
+                            ui.label(&found.summary);
+                            let is_open = save_file.path() == Some(found.location.path.as_path());
+                            if is_open {
+                                ui.label("Open");
+                            } else if ui.button("Open").clicked() {
+                                picked = Some(i);
+                            }
+                            ui.end_row();
+                        }
+                    });
+
+                egui::Sides::new().show(
+                    ui,
+                    |_ui| {},
+                    |ui| {
+                        if ui.button("Close").clicked() {
+                            ui.close();
+                        }
+                    },
+                );
+            });
+
+        if let Some(i) = picked {
+            self.show_save_picker_modal = false;
+            let path = self.save_locations[i].location.path.clone();
+            self.open_save_location(save_file, path);
+        } else if modal.should_close() {
+            self.show_save_picker_modal = false;
+        }
+    }
+
+    fn show_bitfield_modal(&mut self, ctx: &Context, save_file: &mut SaveFile) {
+        let Some((var_data, array_index)) = self.bitfield_editing else {
+            return;
+        };
+        let offset = var_data.offset + array_index.unwrap_or(0) as u32 * 4;
+
+        let save_data = save_file.as_bytes_mut();
+        let value = match array_index {
+            Some(i) => get_int_array_value_from_save_data(
+                save_data,
+                var_data.slot_base_add,
+                var_data.offset,
+                &var_data.int_type,
+                i,
+            ),
+            None => get_int_value_from_save_data(
+                save_data,
+                var_data.slot_base_add,
+                offset,
+                &var_data.int_type,
+            ),
+        };
+        let value = match value {
+            Ok(value) => value,
+            Err(e) => {
+                self.bitfield_editing = None;
+                self.report_error(Err(e));
+                return;
+            }
+        };
+
+        let mut new_value = value;
+        let modal =
+            eframe::egui::Modal::new(eframe::egui::Id::new("Edit Bitfield")).show(ctx, |ui| {
+                ui.set_width(450.0);
+
+                match array_index {
+                    Some(i) => ui.heading(format!("{} [{}]", var_data.variable_name_simple, i)),
+                    None => ui.heading(var_data.variable_name_simple),
+                };
+
+                ui.separator();
+
+                egui::Grid::new("bitfield_grid").show(ui, |ui| {
+                    for bit in 0..32 {
+                        let mut is_set = new_value & (1 << bit) != 0;
+                        if ui.checkbox(&mut is_set, format!("Bit {}", bit)).changed() {
+                            new_value ^= 1 << bit;
+                        }
+                        if bit % 4 == 3 {
+                            ui.end_row();
+                        }
+                    }
+                });
+
+                ui.separator();
+
+                egui::Sides::new().show(
+                    ui,
+                    |ui| {
+                        ui.label(format!("Value: {}", new_value));
+                    },
+                    |ui| {
+                        if ui.button("Done").clicked() {
+                            ui.close();
+                        }
+                    },
+                );
+            });
+
+        if new_value != value {
+            self.report_error(modify_save_data(
+                save_data,
+                var_data.slot_base_add,
+                offset,
+                var_data.int_type,
+                new_value,
+            ));
+            self.edited_save_file = true;
+        }
+        if modal.should_close() {
+            self.bitfield_editing = None;
+        }
+    }
+
+    fn report_error(&mut self, result: Result<(), SaveError>) {
+        if let Err(e) = result {
+            println!("ERROR: {}", e);
+            self.error_message = Some(e.to_string());
+        }
+    }
+
+    fn show_error_modal(&mut self, ctx: &Context) {
+        let Some(error_message) = &self.error_message else {
+            return;
+        };
+
+        let modal = eframe::egui::Modal::new(eframe::egui::Id::new("Error")).show(ctx, |ui| {
+            ui.set_width(450.0);
+
+            ui.heading("Error");
+
+            ui.separator();
+
+            ui.label(error_message);
+
+            egui::Sides::new().show(
+                ui,
+                |_ui| {},
+                |ui| {
+                    if ui.button("Ok").clicked() {
+                        ui.close();
+                    }
+                },
+            );
+        });
+
+        if modal.should_close() {
+            self.error_message = None;
         }
     }
 }
@@ -0,0 +1,144 @@
+use std::ops::Range;
+
+use crate::{
+    EXPECTED_SAVE_FILE_SIZE,
+    layout::{SaveSection, slot_coverage},
+    save_data_info::{
+        SAVE_FILE_HEADER_FIELDS, SAVE_FILE_HEADER_SIZE, SAVE_SLOT_BASE_ADDRESSES,
+        SYSTEM_AREA_START, SaveDataVar,
+    },
+};
+
+/// Which version of some bytes is kept when the file changed on disk while it was being edited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeSide {
+    Editor,
+    Disk,
+}
+
+/// Bytes of the save that were changed in the editor, on disk, or in both, since the file was
+/// read, and which of the changes is kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeItem {
+    pub section: SaveSection,
+    /// Variables stored in the bytes, empty when they're unknown.
+    pub vars: Vec<SaveDataVar>,
+    /// Position of the bytes in the whole file.
+    pub range: Range<usize>,
+    pub changed_in_editor: bool,
+    pub changed_on_disk: bool,
+    pub side: MergeSide,
+}
+
+impl MergeItem {
+    /// Both sides changed the bytes, and not in the same way.
+    pub fn is_conflict(&self) -> bool {
+        self.changed_in_editor && self.changed_on_disk
+    }
+}
+
+/// Splits the whole file in the parts that are merged on their own: the header variables, the
+/// ranges of `slot_coverage` in every slot and the system area.
+fn merge_units() -> Vec<(SaveSection, Vec<SaveDataVar>, Range<usize>)> {
+    let mut units = vec![];
+
+    let mut header_end = 0;
+    for field in SAVE_FILE_HEADER_FIELDS {
+        let start = field.offset as usize;
+        let end = start + field.int_type.size() as usize;
+        if header_end < start {
+            units.push((SaveSection::Header, vec![], header_end..start));
+        }
+        units.push((SaveSection::Header, vec![field.var], start..end));
+        header_end = header_end.max(end);
+    }
+    if header_end < SAVE_FILE_HEADER_SIZE as usize {
+        units.push((
+            SaveSection::Header,
+            vec![],
+            header_end..SAVE_FILE_HEADER_SIZE as usize,
+        ));
+    }
+
+    let coverage = slot_coverage();
+    for (slot, base) in (1..).zip(SAVE_SLOT_BASE_ADDRESSES) {
+        let base = base as usize;
+        for range in &coverage {
+            units.push((
+                SaveSection::Slot(slot),
+                range.vars.clone(),
+                base + range.start as usize..base + range.end as usize,
+            ));
+        }
+    }
+
+    units.push((
+        SaveSection::SystemArea,
+        vec![],
+        SYSTEM_AREA_START as usize..EXPECTED_SAVE_FILE_SIZE,
+    ));
+    units
+}
+
+/// Compares the file as it was read (`base`), as it is in the editor and as it is now on disk.
+/// Parts changed on one side only keep that change, parts changed on both are kept as they are
+/// in the editor until `side` is changed.
+pub fn merge_items(base: &[u8], editor: &[u8], disk: &[u8]) -> Vec<MergeItem> {
+    merge_units()
+        .into_iter()
+        .filter_map(|(section, vars, range)| {
+            let base_bytes = base.get(range.clone());
+            let editor_bytes = editor.get(range.clone());
+            let disk_bytes = disk.get(range.clone());
+            if editor_bytes == disk_bytes {
+                return None;
+            }
+            let changed_in_editor = editor_bytes != base_bytes;
+            let changed_on_disk = disk_bytes != base_bytes;
+            Some(MergeItem {
+                section,
+                vars,
+                range,
+                changed_in_editor,
+                changed_on_disk,
+                side: if changed_in_editor {
+                    MergeSide::Editor
+                } else {
+                    MergeSide::Disk
+                },
+            })
+        })
+        .collect()
+}
+
+/// The file with the side picked in every item, `items` come from `merge_items`. The bytes
+/// without an item are the same in the editor and on disk.
+pub fn merge(editor: &[u8], disk: &[u8], items: &[MergeItem]) -> Vec<u8> {
+    let mut merged = editor.to_vec();
+    for item in items {
+        if item.side == MergeSide::Disk
+            && let (Some(merged_bytes), Some(disk_bytes)) = (
+                merged.get_mut(item.range.clone()),
+                disk.get(item.range.clone()),
+            )
+        {
+            merged_bytes.copy_from_slice(disk_bytes);
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn units_cover_the_whole_file() {
+        let units = merge_units();
+        assert_eq!(units[0].2.start, 0);
+        assert_eq!(units.last().unwrap().2.end, EXPECTED_SAVE_FILE_SIZE);
+        for pair in units.windows(2) {
+            assert_eq!(pair[0].2.end, pair[1].2.start, "{:?}", pair);
+        }
+    }
+}
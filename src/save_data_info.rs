@@ -1,129 +1,1030 @@
 use enum_iterator::Sequence;
 
-#[derive(Debug, PartialEq, Sequence, Clone, Default)]
-pub enum SaveDataVar {
+use crate::error::SaveError;
+
+pub const LEVELS_COUNT: u32 = 40;
+pub const MAZES_COUNT: u32 = 15;
+
+/// Builds `SaveDataVar`, `SAVE_FIELDS` and `SAVE_FILE_HEADER_FIELDS` from a single list, so adding
+/// a variable to the editor is only adding an entry to the `save_data_fields!` call below.
+/// The header variables come first in the list, but the enum has the slot variables first and the
+/// header ones after them, in the same order as the tables, which lets `SaveDataVar::field` index
+/// the tables with the variant number.
+macro_rules! save_data_fields {
+    (
+        header {$(
+            $header_var:ident { $($header_field:tt)* },
+        )*}
+        $(
+            $(#[$attr:meta])*
+            $var:ident { $($field:tt)* },
+        )*
+    ) => {
+        #[derive(Debug, PartialEq, Eq, Sequence, Clone, Copy, Default)]
+        pub enum SaveDataVar {
+            $($(#[$attr])* $var,)*
+            $($header_var,)*
+        }
+
+        /// Every variable of a save slot, in the same order as `SaveDataVar`.
+        pub static SAVE_FIELDS: &[FieldDef] = &[$(
+            save_data_fields!(@field $var { $($field)* }),
+        )*];
+
+        /// Every variable of the file header, the bytes before the first save slot.
+        pub static SAVE_FILE_HEADER_FIELDS: &[FieldDef] = &[$(
+            save_data_fields!(@field $header_var { $($header_field)* }),
+        )*];
+    };
+    (@field $var:ident {
+        $(name: $name:literal,)?
+        simple_name: $simple_name:literal,
+        offset: $offset:expr,
+        int_type: $int_type:expr,
+        category: $category:ident,
+        $(basic: $basic:literal,)?
+        $(read_only: $read_only:literal,)?
+        $(values: $values:expr,)?
+    }) => {
+        FieldDef {
+            var: SaveDataVar::$var,
+            variable_name: save_data_fields!(@values $($name)?),
+            variable_name_simple: $simple_name,
+            offset: $offset,
+            int_type: {
+                use SaveDataIntType::*;
+                $int_type
+            },
+            category: FieldCategory::$category,
+            basic: save_data_fields!(@basic $($basic)?),
+            read_only: save_data_fields!(@basic $($read_only)?),
+            values: save_data_fields!(@values $($values)?),
+        }
+    };
+    (@basic $basic:literal) => {
+        $basic
+    };
+    (@basic) => {
+        false
+    };
+    (@values $values:expr) => {
+        Some($values)
+    };
+    (@values) => {
+        None
+    };
+}
+
+save_data_fields! {
+    header {
+        // nothing in the header is confirmed yet, the names are a guess from how the values look
+        // in the saves seen so far and the code names are unknown, so it can't be edited
+        HeaderVersion {
+            simple_name: "Header Version",
+            offset: 0x0,
+            int_type: U32,
+            category: Global,
+            read_only: true,
+        },
+        LastSlotUsed {
+            simple_name: "Last Slot Used",
+            offset: 0x4,
+            int_type: I32,
+            category: Global,
+            read_only: true,
+        },
+        GlobalFlags {
+            simple_name: "Global Flags",
+            offset: 0x8,
+            int_type: Arrayu8(SAVE_FILE_HEADER_SIZE - 0x8),
+            category: Global,
+            read_only: true,
+        },
+    }
     #[default]
-    FileExists,
+    FileExists {
+        name: "m_bExist",
+        simple_name: "File Exists",
+        offset: 0x0,
+        int_type: Bool,
+        category: File,
+    },
     //m_bNameList, whatever this is
-    SaveDate,
-    PlayTimeHours,
-    PlayTimeMinutes,
-    PlayTimeSeconds,
-    StageFlagList,
-    ScoreList,
-    TimeTrialList,
-    TimeTrialCoopList,
-    FriendsFlagList,
-    FriendsTalkList,
-    StageMazeFlagList,
-    LastStageId,
-    LastGameModeId,
-    Lives,
-    MazeFlagList,
-    MazesScoreList,
-    FruitsGetNum,
-    CapsuleGetNum,
-    MedalGetNum,
-    BombDotKillNum,
-    DotKillNum,
-    RevRollKillNum,
-    ButtBounceKillNum,
-    GhostKillNum,
-    MagicKeyUnlocked,
-    TrueEnding,
-    MarathonUnlocked,
-    MarathonHighScore,
-    MarathonClear,
-    LastGISaveArea,
-    LoadInitScene,
-    DLCApplyFlag,
-    JukeBoxBGM,
-    JukeBoxMode,
-    JukeBoxLoop,
-    JukeBoxOrder,
-    JukeBoxFlag,
-    JukeBoxUnlockFlagList,
-    MedalNum,
-    CapsuleFlag,
-    StageCherryFlag,
-    StageStrawberryFlag,
-    StageOrangeFlag,
-    StageAppleFlag,
-    StageMelonFlag,
-    StageCherryNum,
-    StageStrawberryNum,
-    StageOrangeNum,
-    StageAppleNum,
-    StageMelonNum,
-    VillageCherryFlag,
-    VillageStrawberryFlag,
-    VillageOrangeFlag,
-    VillageAppleFlag,
-    VillageMelonFlag,
-    VillageCherryGetNum,
-    VillageStrawberryGetNum,
-    VillageOrangeGetNum,
-    VillageAppleGetNum,
-    VillageMelonGetNum,
+    SaveDate {
+        name: "m_bDateList",
+        simple_name: "Last Save Date",
+        offset: 0x18,
+        int_type: ArrayText(16),
+        category: File,
+    },
+    PlayTimeHours {
+        name: "m_iPlayHours",
+        simple_name: "File Hours",
+        offset: 0x28,
+        int_type: I32,
+        category: File,
+        basic: true,
+    },
+    PlayTimeMinutes {
+        name: "m_iPlayMinutes",
+        simple_name: "File Minutes",
+        offset: 0x2C,
+        int_type: I32,
+        category: File,
+        basic: true,
+    },
+    PlayTimeSeconds {
+        name: "m_iPlaySeconds",
+        simple_name: "File Seconds",
+        offset: 0x30,
+        int_type: I32,
+        category: File,
+        basic: true,
+    },
+    StageFlagList {
+        name: "m_bStageFlagList",
+        simple_name: "Stages Unlocked",
+        offset: 0x34,
+        int_type: Arrayu8(LEVELS_COUNT),
+        category: Stages,
+    },
+    ScoreList {
+        name: "m_iStageScoreList",
+        simple_name: "Scores List",
+        offset: 0x80,
+        int_type: Arrayi32(LEVELS_COUNT),
+        category: Stages,
+        basic: true,
+    },
+    TimeTrialList {
+        name: "m_iStageTimeList",
+        simple_name: "Time Trials List",
+        offset: 0x1AC,
+        int_type: Arrayi32(LEVELS_COUNT),
+        category: Stages,
+        basic: true,
+    },
+    TimeTrialCoopList {
+        name: "m_iStageTimeListCoop",
+        simple_name: "Time Trials Coop List",
+        offset: 0x210,
+        int_type: Arrayi32(LEVELS_COUNT),
+        category: Stages,
+    },
+    FriendsFlagList {
+        name: "m_bFriendFlagList",
+        simple_name: "Friends Flag List",
+        offset: 0x404,
+        int_type: Arrayu8(9),
+        category: Friends,
+    },
+    FriendsTalkList {
+        name: "m_uFriendTalkList",
+        simple_name: "Friends Talk List",
+        offset: 0x414,
+        int_type: Arrayi32(34), // game says u32 even tho theres clear negs
+        category: Friends,
+    },
+    StageMazeFlagList {
+        name: "m_bStageMazeFlagList",
+        simple_name: "Maze Unlocked Flags",
+        offset: 0x450,
+        int_type: Arrayu8(MAZES_COUNT),
+        category: Stages,
+        basic: true,
+    },
+    LastStageId {
+        name: "m_iLastStageId",
+        simple_name: "Last Stage Id",
+        offset: 0x464,
+        int_type: I32,
+        category: Progress,
+    },
+    LastGameModeId {
+        name: "m_iLastGameModeId",
+        simple_name: "Last Game Mode Id",
+        offset: 0x468,
+        int_type: I32,
+        category: Progress,
+    },
+    Lives {
+        name: "m_iStockNum",
+        simple_name: "Lives",
+        offset: 0x46C,
+        int_type: I32,
+        category: Progress,
+        basic: true,
+    },
+    MazeFlagList {
+        name: "m_bMazeFlagList",
+        simple_name: "Maze Flag List",
+        offset: 0x470,
+        int_type: Arrayu8(MAZES_COUNT),
+        category: Stages,
+    },
+    MazesScoreList {
+        name: "m_iMazeScoreList",
+        simple_name: "Mazes Score List",
+        offset: 0x4A4,
+        int_type: Arrayi32(MAZES_COUNT),
+        category: Stages,
+        basic: true,
+    },
+    FruitsGetNum {
+        name: "m_iFruitsGetNum",
+        simple_name: "Fruits Get Number",
+        offset: 0x56C,
+        int_type: I32,
+        category: Stats,
+    },
+    CapsuleGetNum {
+        name: "m_iCapsuleGetNum",
+        simple_name: "Capsule Get Number",
+        offset: 0x570,
+        int_type: I32,
+        category: Stats,
+    },
+    MedalGetNum {
+        name: "m_iMedalGetNum",
+        simple_name: "Medal Get Number",
+        offset: 0x574,
+        int_type: I32,
+        category: Stats,
+    },
+    BombDotKillNum {
+        name: "m_iBombDotKillNum",
+        simple_name: "Bomb Dot Kill Number",
+        offset: 0x578,
+        int_type: I32,
+        category: Stats,
+    },
+    DotKillNum {
+        name: "m_iPacDotKillNum",
+        simple_name: "PacDot Kill Number",
+        offset: 0x57C,
+        int_type: I32,
+        category: Stats,
+    },
+    RevRollKillNum {
+        name: "m_iPacDashKillNum",
+        simple_name: "Rev Roll Kill Number",
+        offset: 0x580,
+        int_type: I32,
+        category: Stats,
+    },
+    ButtBounceKillNum {
+        name: "m_iHipKillNum",
+        simple_name: "Butt Bounce Kill Number",
+        offset: 0x584,
+        int_type: I32,
+        category: Stats,
+    },
+    GhostKillNum {
+        name: "m_iGhostKillNum",
+        simple_name: "Ghost Kill Number",
+        offset: 0x588,
+        int_type: I32,
+        category: Stats,
+    },
+    MagicKeyUnlocked {
+        name: "m_bGetMagicKey",
+        simple_name: "Magic Key Unlocked",
+        offset: 0x58C,
+        int_type: Bool,
+        category: Progress,
+        basic: true,
+    },
+    TrueEnding {
+        name: "m_bAllTrueEnding",
+        simple_name: "True Ending (unused?)",
+        offset: 0x590,
+        int_type: Bool,
+        category: Progress,
+    },
+    MarathonUnlocked {
+        name: "m_bUnlockMarathon",
+        simple_name: "Marathon Unlocked (unused?)",
+        offset: 0x594,
+        int_type: Bool,
+        category: Arcade,
+    },
+    MarathonHighScore {
+        name: "m_iMarathonHighScore",
+        simple_name: "Marathon High Score (unused?)",
+        offset: 0x598,
+        int_type: I32,
+        category: Arcade,
+    },
+    MarathonClear {
+        name: "m_iMarathonClearFlag",
+        simple_name: "Marathon Cleared (unused?)",
+        offset: 0x59C,
+        int_type: I32,
+        category: Arcade,
+    },
+    LastGISaveArea {
+        name: "m_iLastGISaveArea",
+        simple_name: "Last GI Save Area (unused?)",
+        offset: 0x5A0,
+        int_type: I32,
+        category: Progress,
+    },
+    LoadInitScene {
+        name: "m_iLoadInitScene",
+        simple_name: "Load Init Scene",
+        offset: 0x5A4,
+        int_type: I32,
+        category: Progress,
+    },
+    DLCApplyFlag {
+        name: "m_iDLCApplyFlag",
+        simple_name: "DLC Apply Flag",
+        offset: 0x5A8,
+        int_type: I32,
+        category: File,
+    },
+    JukeBoxBGM {
+        name: "m_iJukeBoxBGMKind",
+        simple_name: "Jukebox Music",
+        offset: 0x5AC,
+        int_type: I32,
+        category: JukeBox,
+        basic: true,
+        values: BGM_NAMES,
+    },
+    JukeBoxMode {
+        name: "m_iJukeBoxMode",
+        simple_name: "Jukebox Mode",
+        offset: 0x5B0,
+        int_type: I32,
+        category: JukeBox,
+    },
+    JukeBoxLoop {
+        name: "m_iJukeBoxLoop",
+        simple_name: "Jukebox Loop",
+        offset: 0x5B4,
+        int_type: I32,
+        category: JukeBox,
+    },
+    JukeBoxOrder {
+        name: "m_iJukeBoxOrder",
+        simple_name: "Jukebox Order",
+        offset: 0x5B8,
+        int_type: I32,
+        category: JukeBox,
+    },
+    JukeBoxFlag {
+        name: "m_iJukeBoxFlag",
+        simple_name: "Jukebox Flag",
+        offset: 0x5BC,
+        int_type: I32,
+        category: JukeBox,
+    },
+    JukeBoxUnlockFlagList {
+        name: "m_bJukeBoxUnlockFlagList",
+        simple_name: "Jukebox Songs Unlocked",
+        offset: 0x5C2,
+        int_type: Arrayu8(83),
+        category: JukeBox,
+    },
+    MedalNum {
+        name: "m_iMedalNum",
+        simple_name: "Medals",
+        offset: 0x640,
+        int_type: I32,
+        category: Stages,
+        basic: true,
+    },
+    CapsuleFlag {
+        name: "m_iCapsuleFlag",
+        simple_name: "Capsule Flags",
+        offset: 0x644,
+        int_type: Arrayi32(150),
+        category: Stages,
+    },
+    StageCherryFlag {
+        name: "m_iStageCherryFlag",
+        simple_name: "Cherries Obtained Bitfield",
+        offset: 0x89C,
+        int_type: BitfieldArray(35),
+        category: Fruits,
+    },
+    StageStrawberryFlag {
+        name: "m_iStageStrawberryFlag",
+        simple_name: "Strawberries Obtained Bitfield",
+        offset: 0x9C8,
+        int_type: BitfieldArray(35),
+        category: Fruits,
+    },
+    StageOrangeFlag {
+        name: "m_iStageOrangeFlag",
+        simple_name: "Oranges Obtained Bitfield",
+        offset: 0xAF4,
+        int_type: BitfieldArray(35),
+        category: Fruits,
+    },
+    StageAppleFlag {
+        name: "m_iStageAppleFlag",
+        simple_name: "Apples Obtained Bitfield",
+        offset: 0xC20,
+        int_type: BitfieldArray(35),
+        category: Fruits,
+    },
+    StageMelonFlag {
+        name: "m_iStageMelonFlag",
+        simple_name: "Melons Obtained Bitfield",
+        offset: 0xD4C,
+        int_type: BitfieldArray(35),
+        category: Fruits,
+    },
+    StageCherryNum {
+        name: "m_iStageCherryGetNum",
+        simple_name: "Cherry Per Level",
+        offset: 0xE78,
+        int_type: Arrayi32(35),
+        category: Fruits,
+    },
+    StageStrawberryNum {
+        name: "m_iStageStrawberryGetNum",
+        simple_name: "Strawberry Per Level",
+        offset: 0xFA4,
+        int_type: Arrayi32(35),
+        category: Fruits,
+    },
+    StageOrangeNum {
+        name: "m_iStageOrangeGetNum",
+        simple_name: "Orange Per Level",
+        offset: 0x10D0,
+        int_type: Arrayi32(35),
+        category: Fruits,
+    },
+    StageAppleNum {
+        name: "m_iStageAppleGetNum",
+        simple_name: "Apple Per Level",
+        offset: 0x1FFC,
+        int_type: Arrayi32(35),
+        category: Fruits,
+    },
+    StageMelonNum {
+        name: "m_iStageMelonGetNum",
+        simple_name: "Melon Per Level",
+        offset: 0x1328,
+        int_type: Arrayi32(35),
+        category: Fruits,
+    },
+    VillageCherryFlag {
+        name: "m_iVillageCherryFlag",
+        simple_name: "Village Cherry Flags",
+        offset: 0x1454,
+        int_type: Arrayi32(26),
+        category: Fruits,
+    },
+    VillageStrawberryFlag {
+        name: "m_iVillageStrawberryFlag",
+        simple_name: "Village Strawberry Flags",
+        offset: 0x01610,
+        int_type: Arrayi32(29),
+        category: Fruits,
+    },
+    VillageOrangeFlag {
+        name: "m_iVillageOrangeFlag",
+        simple_name: "Village Orange Flags",
+        offset: 0x17CC,
+        int_type: Arrayi32(17),
+        category: Fruits,
+    },
+    VillageAppleFlag {
+        name: "m_iVillageAppleFlag",
+        simple_name: "Village Apple Flags",
+        offset: 0x1988,
+        int_type: Arrayi32(22),
+        category: Fruits,
+    },
+    VillageMelonFlag {
+        name: "m_iVillageMelonFlag",
+        simple_name: "Village Melon Flags",
+        offset: 0x1B44,
+        int_type: Arrayi32(32),
+        category: Fruits,
+    },
+    VillageCherryGetNum {
+        name: "m_iVillageCherryGetNum",
+        simple_name: "Village Cherries",
+        offset: 0x1D00,
+        int_type: I32,
+        category: Fruits,
+    },
+    VillageStrawberryGetNum {
+        name: "m_iVillageStrawberryGetNum",
+        simple_name: "Village Strawberry",
+        offset: 0x1D04,
+        int_type: I32,
+        category: Fruits,
+    },
+    VillageOrangeGetNum {
+        name: "m_iVillageOrangeGetNum",
+        simple_name: "Village Oranges",
+        offset: 0x1D08,
+        int_type: I32,
+        category: Fruits,
+    },
+    VillageAppleGetNum {
+        name: "m_iVillageAppleGetNum",
+        simple_name: "Village Apples",
+        offset: 0x1D0C,
+        int_type: I32,
+        category: Fruits,
+    },
+    VillageMelonGetNum {
+        name: "m_iVillageMelonGetNum",
+        simple_name: "Village Melons",
+        offset: 0x1D10,
+        int_type: I32,
+        category: Fruits,
+    },
     // bitfield showing the overall game progress with village curscenes
     // with 0 it will play the into of the game
-    VillageGFFlag,
+    VillageGFFlag {
+        name: "m_iVillageGFFlag",
+        simple_name: "Village GF Bitfield",
+        offset: 0x1D14,
+        int_type: Bitfield,
+        category: Progress,
+    },
     // camera settings
-    CameraMode,
-    CameraSpeedY,
-    CameraSpeedX,
-    CameraControlY,
-    CameraControlX,
-    CameraAssistFlag,
-    CameraYAutoRotateFlag,
-    SwimControlY,
-
-    MissionFlag,
+    CameraMode {
+        name: "m_iCameraMode",
+        simple_name: "Camera Mode",
+        offset: 0x1D18,
+        int_type: I32,
+        category: Camera,
+    },
+    CameraSpeedY {
+        name: "m_iCameraSpeedUD",
+        simple_name: "Camera Sensitivity Y",
+        offset: 0x1D1C,
+        int_type: I32,
+        category: Camera,
+        basic: true,
+    },
+    CameraSpeedX {
+        name: "m_iCameraSpeedLR",
+        simple_name: "Camera Sensitivity X",
+        offset: 0x1D20,
+        int_type: I32,
+        category: Camera,
+        basic: true,
+    },
+    CameraControlY {
+        name: "m_iCameraControlUD",
+        simple_name: "Reverse Vertical Camera",
+        offset: 0x1D24,
+        int_type: Bool, // game code says i32
+        category: Camera,
+        basic: true,
+    },
+    CameraControlX {
+        name: "m_iCameraControlLR",
+        simple_name: "Reverse Horizontal Camera",
+        offset: 0x1D28,
+        int_type: Bool, // game code says i32
+        category: Camera,
+        basic: true,
+    },
+    CameraAssistFlag {
+        name: "m_iCameraAssistFlag",
+        simple_name: "Disable Camera Assist",
+        offset: 0x1D2C,
+        int_type: Bool, // game code says i32
+        category: Camera,
+        basic: true,
+    },
+    CameraYAutoRotateFlag {
+        name: "m_iCameraYAutoRotFlag",
+        simple_name: "Disable Player Tracking Camera",
+        offset: 0x1D30,
+        int_type: Bool, // game code says i32
+        category: Camera,
+        basic: true,
+    },
+    SwimControlY {
+        name: "m_iSwimControlUD",
+        simple_name: "Reverse Vertical Swim Control (?)",
+        offset: 0x1D34,
+        int_type: Arrayi32(2),
+        category: Camera,
+    },
+    MissionFlag {
+        name: "m_iMissionFlag",
+        simple_name: "Mission Complete Flags",
+        offset: 0x1D3C,
+        int_type: Arrayi32(107), // number of missions
+        category: Missions,
+    },
     //MissionProgress, // TODO can't figure out what this is, it's always full of 0
-    MissionRewardFlag,
-    PlayerSkinId,
+    MissionRewardFlag {
+        name: "m_iMissionRewardFlag",
+        simple_name: "Mission Reward Flags",
+        offset: 0x21EC,
+        int_type: Arrayi32(LEVELS_COUNT + 1),
+        category: Missions,
+    },
+    PlayerSkinId {
+        name: "m_iPlayerSkinId",
+        simple_name: "Player Skin",
+        offset: 0x23E0,
+        // game says it's an array of 2 but it's easier here to make this 2 separate vars
+        int_type: I32,
+        category: Progress,
+        basic: true,
+        values: COSTUME_NAMES,
+    },
     // array in the decomp but makes sense to treat these and 2 separate i32s
-    PlayerSkinId2,
-    FigureInfo,
-    FigureDisplayInfo,
-    GashaFlag,
-    GashaLotteryNum,
-    FlipKillNum,
-    SupperHipStunNum,
-    SuperDolphinKillNum,
-    UnlockStageSelectFlag,
-    GameLevel,
-    EnterPast,
-    OriginalFlag,
-    OriginalStageNum,
-    OriginalHighScore,
-    PacManiaStageNum,
-    PacmaniaHighScore,
-    PacAttackLevelNum,
-    PacAttackHighScore,
-    HelpFlag,
-    DroneSkinFlag,
-    DroneSkinID,
-    DroneReticleSpeed,
-    DroneReticleColor,
-    DroneVacuumRangeFlag,
-    EarlyBonusFigureFlag,
-
-    KeyConfigP1, // why is this not global :(
-    KeyConfigP2,
+    PlayerSkinId2 {
+        name: "m_iPlayerSkinId",
+        simple_name: "Player Skin P2",
+        offset: 0x23E4,
+        int_type: I32,
+        category: Progress,
+        values: COSTUME_NAMES,
+    },
+    FigureInfo {
+        name: "m_sFigureInfo",
+        simple_name: "Figure Info",
+        offset: 0x23E8,
+        // TODO verify len and how this works
+        int_type: Arrayi32(20),
+        category: Figures,
+    },
+    FigureDisplayInfo {
+        name: "m_sFigureDisplayInfo",
+        simple_name: "Figure Display Info",
+        offset: 0x2BE8,
+        int_type: SFigureDisplayInfoArray(50),
+        category: Figures,
+    },
+    GashaFlag {
+        name: "m_iGashaFlag",
+        simple_name: "Gasha Flag List",
+        offset: 0x2D78,
+        int_type: Arrayi32(100),
+        category: Figures,
+    },
+    GashaLotteryNum {
+        name: "m_iGashaLotteryNum",
+        simple_name: "Gasha Lottery Number",
+        offset: 0x2DA8,
+        int_type: I32,
+        category: Figures,
+    },
+    FlipKillNum {
+        name: "m_iFlipKillNum",
+        simple_name: "Flip Kill Number",
+        offset: 0x2DAC,
+        int_type: I32,
+        category: Stats,
+    },
+    SupperHipStunNum {
+        name: "m_iSuperHipStunNum",
+        simple_name: "Super Butt Bounce Stun Number",
+        offset: 0x2DB0,
+        int_type: I32,
+        category: Stats,
+    },
+    SuperDolphinKillNum {
+        name: "m_iSuperDolphinKillNum",
+        simple_name: "Super Dolphin Kick Kill Number",
+        offset: 0x2DB4,
+        int_type: I32,
+        category: Stats,
+    },
+    UnlockStageSelectFlag {
+        name: "m_uUnlockStageSelectFlag",
+        simple_name: "Unlock Stage Select Bitfield",
+        offset: 0x2DB8,
+        int_type: Bitfield,
+        category: Progress,
+    },
+    GameLevel {
+        name: "m_iGameLevel",
+        simple_name: "Game Level",
+        offset: 0x2DBC,
+        int_type: I32,
+        category: Progress,
+    },
+    EnterPast {
+        name: "m_bEnterPast",
+        simple_name: "Enter Past",
+        offset: 0x2DC0,
+        int_type: Bool,
+        category: Progress,
+    },
+    OriginalFlag {
+        name: "m_iOriginalFlag",
+        simple_name: "Pac-Man Flags",
+        offset: 0x2DC0,
+        int_type: Arrayi32(3),
+        category: Arcade,
+    },
+    OriginalStageNum {
+        name: "m_iOriginalStageNum",
+        simple_name: "Pac-Man Best Stage",
+        offset: 0x2DD0,
+        int_type: I32,
+        category: Arcade,
+    },
+    OriginalHighScore {
+        name: "m_iOriginalHighScore",
+        simple_name: "Pac-Man High Score",
+        offset: 0x2DD4,
+        int_type: I32,
+        category: Arcade,
+        basic: true,
+    },
+    PacManiaStageNum {
+        name: "m_iPacManiaStageNum",
+        simple_name: "Pac-Mania Best Stage",
+        offset: 0x2DD8,
+        int_type: I32,
+        category: Arcade,
+    },
+    PacmaniaHighScore {
+        name: "m_iPacManiaHighScore",
+        simple_name: "Pac-Mania High Score",
+        offset: 0x2DDC,
+        int_type: I32,
+        category: Arcade,
+        basic: true,
+    },
+    PacAttackLevelNum {
+        name: "m_iPacAttackLevelNum",
+        simple_name: "Pac-Attack Best Level",
+        offset: 0x2DE0,
+        int_type: Arrayi32(4),
+        category: Arcade,
+    },
+    PacAttackHighScore {
+        name: "m_iPacAttackHighScore",
+        simple_name: "Pac-Attack High Score",
+        offset: 0x2DF0,
+        int_type: Arrayi32(4),
+        category: Arcade,
+        basic: true,
+    },
+    HelpFlag {
+        name: "m_iHelpFlag",
+        simple_name: "Help Flags",
+        offset: 0x2E00,
+        int_type: Arrayi32(50),
+        category: Progress,
+    },
+    DroneSkinFlag {
+        name: "m_iPlayerDroneSkinId",
+        simple_name: "Drone Skin ID",
+        offset: 0x2EF0,
+        int_type: Arrayi32(10),
+        category: Drone,
+    },
+    DroneSkinID {
+        name: "m_iPlayerDroneSkinFlag",
+        simple_name: "Drone Skin Flag",
+        offset: 0x2EC8,
+        int_type: I32,
+        category: Drone,
+    },
+    DroneReticleSpeed {
+        name: "m_iDroneReticleSpeed",
+        simple_name: "Drone Reticle Speed",
+        offset: 0x2EF4,
+        int_type: I32,
+        category: Drone,
+    },
+    DroneReticleColor {
+        name: "m_iDroneReticleColor",
+        simple_name: "Drone Reticle Color",
+        offset: 0x2EF8,
+        int_type: I32,
+        category: Drone,
+    },
+    DroneVacuumRangeFlag {
+        name: "m_iDroneVacuumRangeFlag",
+        simple_name: "Drone Vacuum Range Flag",
+        offset: 0x2EFC,
+        int_type: I32,
+        category: Drone,
+    },
+    EarlyBonusFigureFlag {
+        name: "m_iEarlyBonusFigureFlag",
+        simple_name: "Early Access Bonus Figure",
+        offset: 0x2F00,
+        int_type: I32,
+        category: Figures,
+    },
+    // why is this not global :(
+    KeyConfigP1 {
+        name: "m_keyconfigSave1P",
+        simple_name: "Inputs Config",
+        offset: 0x2F04,
+        int_type: Arrayi32(672),
+        category: Controls,
+        basic: true,
+    },
+    KeyConfigP2 {
+        name: "m_keyconfigSave2P",
+        simple_name: "Inputs Config Player 2",
+        offset: 0x3984,
+        int_type: Arrayi32(672),
+        category: Controls,
+    },
     // sonic update exclusive
-    GashaDLCFlag,
-    FigureInfoDLC,
-    EnterSonic,
-    VillageSonicFlag,
-    LoadInitSceneCollab,
-    LoadInitSceneKind,
-    LastStageIdCollab,
-    LastStageIdKindCollab,
-    PlayerSkinIdCollab,
-    FigureDisplayInfoCollab,
-    JukeBoxBGMCollab,
-    JukeBoxModeCollab,
+    GashaDLCFlag {
+        name: "m_iGashaDLCFlag",
+        simple_name: "Gasha DLC Flag",
+        offset: 0x4404,
+        int_type: Arrayi32(4),
+        category: Sonic,
+    },
+    FigureInfoDLC {
+        name: "m_sFigureInfoDLC",
+        simple_name: "Figure Info DLC",
+        offset: 0x4414,
+        int_type: Arrayi32(16),
+        category: Sonic,
+    },
+    EnterSonic {
+        name: "m_bEnterSonic",
+        simple_name: "Enter Sonic",
+        offset: 0x4454,
+        int_type: Bool,
+        category: Sonic,
+    },
+    VillageSonicFlag {
+        name: "m_uVillageSonicFlag",
+        simple_name: "Village Sonic Flag",
+        offset: 0x4458,
+        int_type: Bitfield,
+        category: Sonic,
+    },
+    LoadInitSceneCollab {
+        name: "m_iLoadInitSceneCollabo",
+        simple_name: "Load Init Scene (Sonic)",
+        offset: 0x445C,
+        int_type: I32,
+        category: Sonic,
+    },
+    LoadInitSceneKind {
+        name: "m_iLoadInitSceneKind",
+        simple_name: "Load Init Scene Kind",
+        offset: 0x4560,
+        int_type: I32,
+        category: Sonic,
+    },
+    LastStageIdCollab {
+        name: "m_iLastStageIdCollabo",
+        simple_name: "Last Stage ID (Sonic)",
+        offset: 0x4564,
+        int_type: I32,
+        category: Sonic,
+    },
+    LastStageIdKindCollab {
+        name: "m_iLastStageIdKind",
+        simple_name: "Last Stage ID Kind (Sonic)",
+        offset: 0x4568,
+        int_type: I32,
+        category: Sonic,
+    },
+    PlayerSkinIdCollab {
+        name: "m_iPlayerSkinIdCollabo",
+        simple_name: "Player Skin (Sonic)",
+        offset: 0x456C,
+        int_type: I32,
+        category: Sonic,
+        basic: true,
+        values: COSTUME_COLLAB_NAMES,
+    },
+    FigureDisplayInfoCollab {
+        name: "m_iFigureDisplayInfoCollabo",
+        simple_name: "Figure Display Info (Sonic)",
+        offset: 0x4570,
+        int_type: SFigureDisplayInfoArray(57),
+        category: Sonic,
+    },
+    JukeBoxBGMCollab {
+        name: "m_iJukeBoxBGMKindCollabo",
+        simple_name: "Jukebox Music (Sonic)",
+        offset: 0x4638,
+        int_type: I32,
+        category: Sonic,
+        basic: true,
+        values: BGM_COLLAB_NAMES,
+    },
+    JukeBoxModeCollab {
+        name: "m_iJukeBoxModeCollabo",
+        simple_name: "Jukebox Mode (Sonic)",
+        offset: 0x463C,
+        int_type: I32,
+        category: Sonic,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Sequence, Clone, Copy)]
+pub enum FieldCategory {
+    File,
+    Progress,
+    Stages,
+    Friends,
+    Stats,
+    Arcade,
+    JukeBox,
+    Fruits,
+    Camera,
+    Missions,
+    Figures,
+    Drone,
+    Controls,
+    Sonic,
+    Global,
+}
+
+impl FieldCategory {
+    pub fn name(&self) -> &'static str {
+        match self {
+            FieldCategory::File => "File",
+            FieldCategory::Progress => "Progress",
+            FieldCategory::Stages => "Stages",
+            FieldCategory::Friends => "Friends",
+            FieldCategory::Stats => "Stats",
+            FieldCategory::Arcade => "Arcade Games",
+            FieldCategory::JukeBox => "Jukebox",
+            FieldCategory::Fruits => "Fruits",
+            FieldCategory::Camera => "Camera",
+            FieldCategory::Missions => "Missions",
+            FieldCategory::Figures => "Figures",
+            FieldCategory::Drone => "Drone",
+            FieldCategory::Controls => "Controls",
+            FieldCategory::Sonic => "Sonic Update",
+            FieldCategory::Global => "Global Data",
+        }
+    }
+}
+
+/// Where a variable lives inside a save slot, or the header for `FieldCategory::Global`, and how to
+/// show it, slot independent.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldDef {
+    pub var: SaveDataVar,
+    /// The name in the game's code, `None` when it isn't known.
+    pub variable_name: Option<&'static str>,
+    pub variable_name_simple: &'static str,
+    pub offset: u32,
+    pub int_type: SaveDataIntType,
+    pub category: FieldCategory,
+    /// Shown when the editor only shows the simple data.
+    pub basic: bool,
+    /// Only shown, the editor and the command line don't write it.
+    pub read_only: bool,
+    /// Names of the values the variable can have, for variables that work like an enum.
+    pub values: Option<ValueNames>,
+}
+
+impl FieldDef {
+    pub fn at_slot(&self, slot: u8) -> Result<SaveFileData, SaveError> {
+        Ok(SaveFileData {
+            var: self.var,
+            variable_name: self.variable_name,
+            variable_name_simple: self.variable_name_simple,
+            slot_base_add: get_save_slot_base_add(slot)?,
+            offset: self.offset,
+            int_type: self.int_type,
+        })
+    }
+
+    /// The header variables are at the start of the file, outside of every slot.
+    pub fn at_header(&self) -> SaveFileData {
+        SaveFileData {
+            var: self.var,
+            variable_name: self.variable_name,
+            variable_name_simple: self.variable_name_simple,
+            slot_base_add: 0,
+            offset: self.offset,
+            int_type: self.int_type,
+        }
+    }
+}
+
+impl SaveDataVar {
+    /// Whether the variable is in the file header instead of a save slot.
+    pub fn is_header(self) -> bool {
+        self as usize >= SAVE_FIELDS.len()
+    }
+
+    pub fn field(self) -> &'static FieldDef {
+        let i = self as usize;
+        match SAVE_FIELDS.get(i) {
+            Some(field) => field,
+            None => &SAVE_FILE_HEADER_FIELDS[i - SAVE_FIELDS.len()],
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -131,185 +1032,229 @@ pub enum SaveDataIntType {
     Bool,
     U32,
     I32,
+    F32,
     Arrayi32(u32), // stores its length
     //Arrayu32(u32),
     Arrayu8(u32),
     ArrayText(u32),
     SFigureDisplayInfoArray(u32),
+    /// What each bit does isn't known yet, they're shown and edited by number.
+    Bitfield,
+    BitfieldArray(u32), // stores its length
+}
+
+/// The number of every bit that is set, like "Bit 0, Bit 2".
+pub fn bitfield_to_bits(value: i64) -> String {
+    let bits: Vec<String> = (0..32)
+        .filter(|bit| value & (1 << bit) != 0)
+        .map(|bit| format!("Bit {}", bit))
+        .collect();
+    if bits.is_empty() {
+        "None".to_string()
+    } else {
+        bits.join(", ")
+    }
 }
 
-#[derive(Debug, Clone)]
+impl SaveDataIntType {
+    /// How many bytes the value takes in the save file.
+    pub fn size(&self) -> u32 {
+        match self {
+            SaveDataIntType::Bool => 1,
+            SaveDataIntType::U32 | SaveDataIntType::I32 | SaveDataIntType::F32 => 4,
+            SaveDataIntType::Arrayi32(len) => len * 4,
+            SaveDataIntType::Arrayu8(len) | SaveDataIntType::ArrayText(len) => *len,
+            SaveDataIntType::SFigureDisplayInfoArray(len) => len * 8,
+            SaveDataIntType::Bitfield => 4,
+            SaveDataIntType::BitfieldArray(len) => len * 4,
+        }
+    }
+
+    /// Short name of the type for messages, without the bit names of bitfields.
+    pub fn name(&self) -> String {
+        match self {
+            SaveDataIntType::Bitfield => "Bitfield".to_string(),
+            SaveDataIntType::BitfieldArray(len) => format!("BitfieldArray({})", len),
+            _ => format!("{:?}", self),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
 pub struct SaveFileData {
     pub var: SaveDataVar,
-    pub variable_name: String,
-    pub variable_name_simple: String,
+    pub variable_name: Option<&'static str>,
+    pub variable_name_simple: &'static str,
     pub slot_base_add: u32,
     pub offset: u32,
     pub int_type: SaveDataIntType,
 }
 
-pub fn get_save_slot_base_add(slot: u8) -> u32 {
-    match slot {
-        1 => 0x298,
-        2 => 0x7BC8,
-        3 => 0xF4F8,
-        4 => 0x16E28,
-        _ => {
-            println!(
-                "Invalid slot sent! Using slot 1. Slot in function: {}",
-                slot
-            );
-            0x298
-        }
+/// Distance between the start of two save slots, no variable of a slot can go past this.
+pub const SAVE_SLOT_SIZE: u32 = 0x7930;
+
+/// Size of the data before the first save slot.
+pub const SAVE_FILE_HEADER_SIZE: u32 = 0x298;
+
+/// Start of the data after the last save slot, until the end of the file. Nothing in it is known
+/// yet, it's probably the system data or the options.
+pub const SYSTEM_AREA_START: u32 = 0x1E758;
+
+/// Values an enum-like variable can have, with the name the game gives them. Only the jukebox
+/// music and the costumes have one for now, the values of the mode and option variables, like
+/// `CameraMode`, `GameLevel` or `LoadInitSceneKind`, still have to be checked in a save.
+pub type ValueNames = &'static [(i64, &'static str)];
+
+/// The value with its name, like "2 Pac-Village".
+pub fn value_name(value: i64, values: ValueNames) -> String {
+    match values.iter().find(|(known_value, _)| *known_value == value) {
+        Some((_, name)) => format!("{} {}", value, name),
+        None => format!("{} (Invalid)", value),
     }
 }
 
-pub fn bgm_music_str_to_name(music: i32) -> String {
-    let music_name = match music {
-        -1 => "(Disabled, using DLC music)",
-        0 => "Opening",
-        1 => "Prologue",
-        2 => "Pac-Village",
-        3 => "The Bear Basics",
-        4 => "Canyon Chaos",
-        5 => "Pac-Dot Pond",
-        6 => "Clyde's Frog",
-        7 => "B-Doing Woods",
-        8 => "Treewood Forest",
-        9 => "Butane Pain",
-        10 => "Inky's Whimsy",
-        11 => "Pac-Ranger's Theme",
-        12 => "Ice River Run",
-        13 => "Avalanche Alley",
-        14 => "Blade Mountain",
-        15 => "Pinky's Revenge",
-        16 => "Into the Volcano!",
-        17 => "Volcanic Panic",
-        18 => "Magma Opus",
-        19 => "Blinky in the Caldera",
-        20 => "Scuba Duba",
-        21 => "Shark Attack",
-        22 => "Yellow Pac-Marine",
-        23 => "Whale on a Sub",
-        24 => "Haunted Broadwalk",
-        25 => "Night Crawling",
-        26 => "Ghost Bayou",
-        27 => "Climax",
-        28 => "Spooky (Phase 1)",
-        29 => "Spooky (Phase 2)",
-        30 => "Ending",
-        31 => "Credits",
-        32 => "Results",
-        33 => "Golden Fruit",
-        34 => "Power Pellets",
-        35 => "Pac-Village (Arcade Remix)",
-        36 => "Clyde's Frog (Speedy Mix)",
-        37 => "Inky's Whimsy (Speedy Mix)",
-        38 => "Pinky's Revenge (Speedy Mix)",
-        40 => "Whale on a Sub (Speedy Mix)",
-        42 => "Game Over",
-        46 => "Deadly Poisonous Meadows",
-        47 => "A Long Poisonous Tongue",
-        48 => "Harsh Harsh Winds",
-        49 => "Hunter of Darkness",
-        50 => "Pro Thunder Skater",
-        51 => "Boom! Boom! Clap!",
-        52 => "Hot! Fire Trouble",
-        53 => "Blinky in the Caldera",
-        54 => "Sharks Everywhere",
-        55 => "Pac-Marine Battle!",
-        56 => "Clumsy Bayou",
-        57 => "Legendary Story (Phase 1)",
-        58 => "Legendary Story (Phase 2)",
-        59 => "A Long Poisonous Tongue (Speedy Mix)",
-        60 => "Hunter of Darkness (Speedy Mix)",
-        61 => "Boom! Boom! Clap! (Speedy Mix)",
-        62 => "Pac-Marine Battle! (Speedy Mix)",
-        65 => "Flying Dark Shadow",
-        66 => "Pac-Village (Pac-Knight)",
-        70 => "Ending (Pac-Knight)",
-        71 => "PAC-MANIAC45",
-        72 => "Start Music",
-        73 => "Coffee Break Music",
-        75 => "Block Town Music (Japan Version)",
-        76 => "Pacman's Park (Japan Version)",
-        77 => "Sandbox Land",
-        78 => "Jungly Steps",
-        79 => "Menu Selection",
-        80 => "Puzzle Fever",
-        81 => "The Can-Can",
-        82 => "Blinky in the Caldera (Phase 1)",
-        83 => "Blinky in the Caldera (Phase 2)",
-        84 => "Burning Hot Beats (Phase 1)",
-        85 => "Burning Hot Beats (Phase 2)",
-        _ => "(Invalid)",
-    }
-    .to_string();
+/// Where each of the 4 save slots starts in the file.
+pub const SAVE_SLOT_BASE_ADDRESSES: [u32; 4] = [0x298, 0x7BC8, 0xF4F8, 0x16E28];
 
-    format!("{} {}", music, music_name)
+/// Where the slot starts in the file, slots go from 1 to 4.
+pub fn get_save_slot_base_add(slot: u8) -> Result<u32, SaveError> {
+    (slot as usize)
+        .checked_sub(1)
+        .and_then(|i| SAVE_SLOT_BASE_ADDRESSES.get(i))
+        .copied()
+        .ok_or(SaveError::InvalidSlot(slot))
 }
 
-pub fn bgm_music_str_to_name_collab(music: i32) -> String {
-    let music_name = match music {
-        -1 => "(Disabled, using non DLC music)",
-        // to use in m_iJukeBoxBGMKindCollabo
-        86 => "Supersonic Tricky Railroad",
-        87 => "South Pac-Island",
-        88 => "GREEN HILL: ACT1",
-        89 => "GREEN HILL: ACT2 - Normal",
-        93 => "BOSS BATTLE: DEATH EGG ROBOT",
-        94 => "Results",
-        95 => "Results (S-Rank)",
-        96 => "Act Clear",
-        _ => "(Invalid)",
-    }
-    .to_string();
+pub const BGM_NAMES: ValueNames = &[
+    (-1, "(Disabled, using DLC music)"),
+    (0, "Opening"),
+    (1, "Prologue"),
+    (2, "Pac-Village"),
+    (3, "The Bear Basics"),
+    (4, "Canyon Chaos"),
+    (5, "Pac-Dot Pond"),
+    (6, "Clyde's Frog"),
+    (7, "B-Doing Woods"),
+    (8, "Treewood Forest"),
+    (9, "Butane Pain"),
+    (10, "Inky's Whimsy"),
+    (11, "Pac-Ranger's Theme"),
+    (12, "Ice River Run"),
+    (13, "Avalanche Alley"),
+    (14, "Blade Mountain"),
+    (15, "Pinky's Revenge"),
+    (16, "Into the Volcano!"),
+    (17, "Volcanic Panic"),
+    (18, "Magma Opus"),
+    (19, "Blinky in the Caldera"),
+    (20, "Scuba Duba"),
+    (21, "Shark Attack"),
+    (22, "Yellow Pac-Marine"),
+    (23, "Whale on a Sub"),
+    (24, "Haunted Broadwalk"),
+    (25, "Night Crawling"),
+    (26, "Ghost Bayou"),
+    (27, "Climax"),
+    (28, "Spooky (Phase 1)"),
+    (29, "Spooky (Phase 2)"),
+    (30, "Ending"),
+    (31, "Credits"),
+    (32, "Results"),
+    (33, "Golden Fruit"),
+    (34, "Power Pellets"),
+    (35, "Pac-Village (Arcade Remix)"),
+    (36, "Clyde's Frog (Speedy Mix)"),
+    (37, "Inky's Whimsy (Speedy Mix)"),
+    (38, "Pinky's Revenge (Speedy Mix)"),
+    (40, "Whale on a Sub (Speedy Mix)"),
+    (42, "Game Over"),
+    (46, "Deadly Poisonous Meadows"),
+    (47, "A Long Poisonous Tongue"),
+    (48, "Harsh Harsh Winds"),
+    (49, "Hunter of Darkness"),
+    (50, "Pro Thunder Skater"),
+    (51, "Boom! Boom! Clap!"),
+    (52, "Hot! Fire Trouble"),
+    (53, "Blinky in the Caldera"),
+    (54, "Sharks Everywhere"),
+    (55, "Pac-Marine Battle!"),
+    (56, "Clumsy Bayou"),
+    (57, "Legendary Story (Phase 1)"),
+    (58, "Legendary Story (Phase 2)"),
+    (59, "A Long Poisonous Tongue (Speedy Mix)"),
+    (60, "Hunter of Darkness (Speedy Mix)"),
+    (61, "Boom! Boom! Clap! (Speedy Mix)"),
+    (62, "Pac-Marine Battle! (Speedy Mix)"),
+    (65, "Flying Dark Shadow"),
+    (66, "Pac-Village (Pac-Knight)"),
+    (70, "Ending (Pac-Knight)"),
+    (71, "PAC-MANIAC45"),
+    (72, "Start Music"),
+    (73, "Coffee Break Music"),
+    (75, "Block Town Music (Japan Version)"),
+    (76, "Pacman's Park (Japan Version)"),
+    (77, "Sandbox Land"),
+    (78, "Jungly Steps"),
+    (79, "Menu Selection"),
+    (80, "Puzzle Fever"),
+    (81, "The Can-Can"),
+    (82, "Blinky in the Caldera (Phase 1)"),
+    (83, "Blinky in the Caldera (Phase 2)"),
+    (84, "Burning Hot Beats (Phase 1)"),
+    (85, "Burning Hot Beats (Phase 2)"),
+];
 
-    format!("{} {}", music, music_name)
-}
+pub const BGM_COLLAB_NAMES: ValueNames = &[
+    (-1, "(Disabled, using non DLC music)"),
+    // to use in m_iJukeBoxBGMKindCollabo
+    (86, "Supersonic Tricky Railroad"),
+    (87, "South Pac-Island"),
+    (88, "GREEN HILL: ACT1"),
+    (89, "GREEN HILL: ACT2 - Normal"),
+    (93, "BOSS BATTLE: DEATH EGG ROBOT"),
+    (94, "Results"),
+    (95, "Results (S-Rank)"),
+    (96, "Act Clear"),
+];
 
-pub fn costume_int_to_name(costume_id: i32) -> String {
-    let costume_name = match costume_id {
-        -1 => "(Disabled)",
-        0x00 => "No Costume",
-        0x01 => "Green Hunter",
-        0x02 => "Brown Hunter",
-        0x03 => "Blue Hunter",
-        0x04 => "Blue Street",
-        0x05 => "Red Street",
-        0x06 => "Pink Street",
-        0x07 => "Brown Cowboy",
-        0x08 => "Red Cowboy",
-        0x09 => "Pink Cowboy",
-        0x0a => "Brown Ushanka",
-        0x0b => "Red Ushanka",
-        0x0c => "Blue Ushanka",
-        0x0d => "White Explorer",
-        0x0e => "Green Explorer",
-        0x0f => "Pink Explorer",
-        0x10 => "Blue Diver",
-        0x11 => "Green Diver",
-        0x12 => "Pink Diver",
-        0x13 => "Blinky",
-        0x14 => "Inky",
-        0x15 => "Clyde",
-        0x16 => "Pinky",
-        0x17 => "Black Magician",
-        0x18 => "Purple Magician",
-        0x19 => "Green Magician",
-        0x1a => "Pac-Knight",
-        0x1b => "Toc-Man",
-        0x1c => "Pac-Wizard",
-        0x1d => "Orson",
-        0x1e => "Pac-Land",
-        0x1f => "Spooky",
-        0x20 => "Sonic",
-        _ => "(Invalid)",
-    }
-    .to_string();
+pub const COSTUME_NAMES: ValueNames = &[
+    (-1, "(Disabled)"),
+    (0x00, "No Costume"),
+    (0x01, "Green Hunter"),
+    (0x02, "Brown Hunter"),
+    (0x03, "Blue Hunter"),
+    (0x04, "Blue Street"),
+    (0x05, "Red Street"),
+    (0x06, "Pink Street"),
+    (0x07, "Brown Cowboy"),
+    (0x08, "Red Cowboy"),
+    (0x09, "Pink Cowboy"),
+    (0x0a, "Brown Ushanka"),
+    (0x0b, "Red Ushanka"),
+    (0x0c, "Blue Ushanka"),
+    (0x0d, "White Explorer"),
+    (0x0e, "Green Explorer"),
+    (0x0f, "Pink Explorer"),
+    (0x10, "Blue Diver"),
+    (0x11, "Green Diver"),
+    (0x12, "Pink Diver"),
+    (0x13, "Blinky"),
+    (0x14, "Inky"),
+    (0x15, "Clyde"),
+    (0x16, "Pinky"),
+    (0x17, "Black Magician"),
+    (0x18, "Purple Magician"),
+    (0x19, "Green Magician"),
+    (0x1a, "Pac-Knight"),
+    (0x1b, "Toc-Man"),
+    (0x1c, "Pac-Wizard"),
+    (0x1d, "Orson"),
+    (0x1e, "Pac-Land"),
+    (0x1f, "Spooky"),
+];
 
-    format!("{} {}", costume_id, costume_name)
-}
+/// The Sonic update has its own costume slot that only takes Sonic.
+pub const COSTUME_COLLAB_NAMES: ValueNames = &[(-1, "(Disabled)"), (0x20, "Sonic")];
 
 pub fn int_to_stage_name(id: usize, pac_village_start: bool) -> String {
     let id_to_match = if pac_village_start { id } else { id + 1 };
@@ -0,0 +1,48 @@
+use chrono::{Local, NaiveDateTime, Timelike};
+
+use crate::error::SaveError;
+
+/// How the game writes the date of the last save into `m_bDateList`, it takes the whole 16 bytes.
+pub const SAVE_DATE_FORMAT: &str = "%Y/%m/%d %H:%M";
+
+/// Parses the text of `SaveDataVar::SaveDate`, `address` is only used for the error.
+pub fn parse_save_date(text: &str, address: usize) -> Result<NaiveDateTime, SaveError> {
+    // the text is read with the whole array, padding included
+    let text = text.trim_end_matches('\0');
+    NaiveDateTime::parse_from_str(text, SAVE_DATE_FORMAT).map_err(|e| SaveError::InvalidValue {
+        address,
+        reason: format!("\"{}\" is not a date like YYYY/MM/DD hh:mm ({})", text, e),
+    })
+}
+
+pub fn format_save_date(date: &NaiveDateTime) -> String {
+    date.format(SAVE_DATE_FORMAT).to_string()
+}
+
+/// The current local time, without the seconds since the game doesn't save them.
+pub fn save_date_now() -> NaiveDateTime {
+    let now = Local::now().naive_local();
+    now.with_second(0)
+        .and_then(|now| now.with_nanosecond(0))
+        .unwrap_or(now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_formats_the_game_format() {
+        let date = parse_save_date("2025/09/26 21:33", 0).unwrap();
+        assert_eq!(format_save_date(&date), "2025/09/26 21:33");
+        assert_eq!(parse_save_date("2025/09/26 21:33\0", 0).unwrap(), date);
+    }
+
+    #[test]
+    fn rejects_invalid_dates() {
+        assert!(parse_save_date("Save Editor REPA", 0).is_err());
+        assert!(parse_save_date("2025/02/30 10:00", 0).is_err());
+        assert!(parse_save_date("2025/09/26 24:00", 0).is_err());
+        assert!(parse_save_date("2025-09-26 21:33", 0).is_err());
+    }
+}
@@ -0,0 +1,513 @@
+use chrono::NaiveDateTime;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::{
+    EXPECTED_SAVE_FILE_SIZE,
+    error::SaveError,
+    merge::{MergeItem, merge, merge_items},
+    new_file::get_new_save_file,
+    save_data_info::{
+        SAVE_SLOT_BASE_ADDRESSES, SAVE_SLOT_SIZE, SYSTEM_AREA_START, SaveDataIntType, SaveDataVar,
+        SaveFileData, get_save_slot_base_add,
+    },
+    save_date::{format_save_date, parse_save_date},
+    save_file_parser::{
+        SFigureDisplayInfo, check_save_file_size, fnv1a_hash, get_figure_info_value_from_save_data,
+        get_file_path, get_float_value_from_save_data, get_int_array_from_save_data,
+        get_int_array_value_from_save_data, get_int_value_from_save_data, get_save_data_bytes,
+        get_save_data_bytes_mut, get_save_file_variable, get_text_value_from_save_data,
+        modify_figure_info, modify_save_data, modify_save_data_float, modify_save_data_text,
+        read_save_file, write_save_file,
+    },
+    slot_data::SlotData,
+    version::{SaveVersion, check_slot_version, detect_version, sonic_update_range},
+};
+
+/// Owned copy of a whole DAT00000.dat file, the 4 save slots are read and edited through
+/// `slot` and `slot_mut`.
+#[derive(Debug, Clone, Default)]
+pub struct SaveFile {
+    data: Vec<u8>,
+    /// The data as it was last read from or written to disk, to know what was edited since.
+    on_disk: Vec<u8>,
+    /// Update of the game that wrote the file, variables of later updates can't be written.
+    version: SaveVersion,
+    /// Version picked by hand, used instead of the detected one, even after reading the file again.
+    version_override: Option<SaveVersion>,
+    /// Where the file was read from, it's written back there.
+    path: Option<PathBuf>,
+    /// The file on disk when `on_disk` was read or written, to notice when something else
+    /// writes it.
+    disk_state: Option<DiskState>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DiskState {
+    modified: Option<SystemTime>,
+    hash: u64,
+}
+
+/// Read only view of one of the 4 save slots.
+#[derive(Debug, Clone, Copy)]
+pub struct SaveSlot<'a> {
+    data: &'a [u8],
+    slot: u8,
+}
+
+/// Editable view of one of the 4 save slots.
+#[derive(Debug)]
+pub struct SaveSlotMut<'a> {
+    data: &'a mut [u8],
+    slot: u8,
+    version: SaveVersion,
+}
+
+impl SaveFile {
+    pub fn from_bytes(data: Vec<u8>) -> Result<SaveFile, SaveError> {
+        check_save_file_size(&data)?;
+        Ok(SaveFile {
+            on_disk: data.clone(),
+            version: detect_version(&data),
+            version_override: None,
+            data,
+            path: None,
+            disk_state: None,
+        })
+    }
+
+    /// A save file full of zeros, where every slot is empty.
+    pub fn empty() -> SaveFile {
+        SaveFile {
+            data: vec![0; EXPECTED_SAVE_FILE_SIZE],
+            on_disk: vec![0; EXPECTED_SAVE_FILE_SIZE],
+            version: SaveVersion::default(),
+            version_override: None,
+            path: None,
+            disk_state: None,
+        }
+    }
+
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<SaveFile, SaveError> {
+        let path = path.as_ref();
+        // the date is read first, a write between both reads is then noticed on the next check
+        let modified = modified_time(path);
+        let mut save_file = SaveFile::from_bytes(read_save_file(path)?)?;
+        save_file.path = Some(path.to_path_buf());
+        save_file.disk_state = Some(DiskState {
+            modified,
+            hash: fnv1a_hash(&save_file.on_disk),
+        });
+        Ok(save_file)
+    }
+
+    /// Reads the save file from the path in `PW2REPAC_SAVE_PATH`, or the game's save folder.
+    pub fn load() -> Result<SaveFile, SaveError> {
+        SaveFile::from_path(get_file_path()?)
+    }
+
+    /// Writes the save file back to where it was read from.
+    pub fn write(&mut self) -> Result<(), SaveError> {
+        let Some(path) = &self.path else {
+            return Err(SaveError::PathNotFound(
+                "no save file is open to write to".to_string(),
+            ));
+        };
+        write_save_file(path, &self.data)?;
+        self.on_disk = self.data.clone();
+        self.disk_state = Some(DiskState {
+            modified: modified_time(path),
+            hash: fnv1a_hash(&self.on_disk),
+        });
+        Ok(())
+    }
+
+    /// Checks if something else, like the game, wrote the file since it was read or written.
+    /// The file is only read again when its modification date changed, and it's only returned
+    /// when its bytes changed too. It keeps the version picked with `set_version`, so it can
+    /// replace this one when there are no edits.
+    pub fn read_disk_changes(&mut self) -> Result<Option<SaveFile>, SaveError> {
+        let (Some(path), Some(disk_state)) = (&self.path, &mut self.disk_state) else {
+            return Ok(None);
+        };
+        if modified_time(path) == disk_state.modified {
+            return Ok(None);
+        }
+        let mut disk = SaveFile::from_path(path)?;
+        disk.version_override = self.version_override;
+        match disk.disk_state {
+            Some(new_state) if new_state.hash != disk_state.hash => Ok(Some(disk)),
+            Some(new_state) => {
+                // only the date changed
+                disk_state.modified = new_state.modified;
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// What changed in the editor and in `disk`, a newer read of the same file, since the file
+    /// was read or written.
+    pub fn disk_merge_items(&self, disk: &SaveFile) -> Vec<MergeItem> {
+        merge_items(&self.on_disk, &self.data, &disk.on_disk)
+    }
+
+    /// Takes a newer read of the same file, from `read_disk_changes`, keeping the edits of the
+    /// editor where `items` say so. The merged data isn't written until the next `write`.
+    pub fn merge_disk_changes(&mut self, disk: SaveFile, items: &[MergeItem]) {
+        self.data = merge(&self.data, &disk.on_disk, items);
+        self.on_disk = disk.on_disk;
+        self.disk_state = disk.disk_state;
+    }
+
+    /// Writes the save file to another path, which is where it's written from now on.
+    pub fn write_to<P: AsRef<Path>>(&mut self, path: P) -> Result<(), SaveError> {
+        self.path = Some(path.as_ref().to_path_buf());
+        self.write()
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// The version detected when the file was read, or the one picked with `set_version`.
+    pub fn version(&self) -> SaveVersion {
+        self.version_override.unwrap_or(self.version)
+    }
+
+    /// Overrides the detected version, for when the detection got it wrong.
+    pub fn set_version(&mut self, version: SaveVersion) {
+        self.version_override = Some(version);
+    }
+
+    /// The version picked with `set_version`, if any.
+    pub fn version_override(&self) -> Option<SaveVersion> {
+        self.version_override
+    }
+
+    /// Slots whose bytes changed since the file was read or last written.
+    pub fn modified_slots(&self) -> Vec<u8> {
+        (1..)
+            .zip(SAVE_SLOT_BASE_ADDRESSES)
+            .filter(|(_, base)| {
+                let start = *base as usize;
+                let end = start + SAVE_SLOT_SIZE as usize;
+                self.data.get(start..end) != self.on_disk.get(start..end)
+            })
+            .map(|(slot, _)| slot)
+            .collect()
+    }
+
+    /// Sets the last save date of every modified slot that exists, like the game does when saving.
+    /// Called after the backup is taken and right before writing, so the stamp is the only change
+    /// that isn't in the backup.
+    pub fn stamp_modified_slots(&mut self, date: &NaiveDateTime) -> Result<(), SaveError> {
+        for slot in self.modified_slots() {
+            if self.slot(slot).exists()? {
+                self.slot_mut(slot).set_date(date)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The bytes after the last slot.
+    pub fn system_area(&self) -> &[u8] {
+        self.data
+            .get(SYSTEM_AREA_START as usize..)
+            .unwrap_or_default()
+    }
+
+    /// Positions inside the system area of the bytes that changed since the file was read or
+    /// last written.
+    pub fn system_area_changes(&self) -> Vec<usize> {
+        let start = SYSTEM_AREA_START as usize;
+        self.system_area()
+            .iter()
+            .zip(self.on_disk.get(start..).unwrap_or_default())
+            .enumerate()
+            .filter(|(_, (byte, byte_on_disk))| byte != byte_on_disk)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Puts back the system area as it was when the file was read or last written.
+    pub fn revert_system_area(&mut self) {
+        let start = SYSTEM_AREA_START as usize;
+        if let (Some(area), Some(area_on_disk)) =
+            (self.data.get_mut(start..), self.on_disk.get(start..))
+        {
+            area.copy_from_slice(area_on_disk);
+        }
+    }
+
+    /// Replaces the whole file with `data`, like a backup, the version is detected again but a
+    /// version picked by hand is kept. It's only written to disk by the next `write`.
+    pub fn restore(&mut self, data: &[u8]) -> Result<(), SaveError> {
+        check_save_file_size(data)?;
+        self.data = data.to_vec();
+        self.version = detect_version(&self.data);
+        Ok(())
+    }
+
+    /// Copies one slot from another save file, like a backup, the rest of the file is kept.
+    /// Fails without copying anything if it changes variables that the save's version doesn't have.
+    pub fn restore_slot(&mut self, from: &SaveFile, slot: u8) -> Result<(), SaveError> {
+        let version = self.version();
+        let from_bytes = from.slot(slot).bytes()?;
+        let start = get_save_slot_base_add(slot)? as usize;
+        let slot_bytes = get_save_data_bytes_mut(&mut self.data, start, SAVE_SLOT_SIZE as usize)?;
+        check_slot_version(version, slot_bytes, from_bytes)?;
+        slot_bytes.copy_from_slice(from_bytes);
+        Ok(())
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+
+    pub fn slot(&self, slot: u8) -> SaveSlot<'_> {
+        SaveSlot {
+            data: &self.data,
+            slot,
+        }
+    }
+
+    pub fn slot_mut(&mut self, slot: u8) -> SaveSlotMut<'_> {
+        let version = self.version();
+        SaveSlotMut {
+            data: &mut self.data,
+            slot,
+            version,
+        }
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+impl<'a> SaveSlot<'a> {
+    pub fn number(&self) -> u8 {
+        self.slot
+    }
+
+    /// Where the variable is in this slot, fails for the variables of the file header.
+    pub fn var(&self, var: SaveDataVar) -> Result<SaveFileData, SaveError> {
+        if var.is_header() {
+            return Err(SaveError::NotInSlot { var });
+        }
+        get_save_file_variable(var, self.slot)
+    }
+
+    pub fn exists(&self) -> Result<bool, SaveError> {
+        Ok(self.get_int(SaveDataVar::FileExists)? != 0)
+    }
+
+    pub fn get_int(&self, var: SaveDataVar) -> Result<i64, SaveError> {
+        let var_data = self.var(var)?;
+        get_int_value_from_save_data(
+            self.data,
+            var_data.slot_base_add,
+            var_data.offset,
+            &var_data.int_type,
+        )
+    }
+
+    pub fn get_int_array(&self, var: SaveDataVar) -> Result<Vec<i64>, SaveError> {
+        let var_data = self.var(var)?;
+        get_int_array_from_save_data(
+            self.data,
+            var_data.slot_base_add,
+            var_data.offset,
+            &var_data.int_type,
+        )
+    }
+
+    pub fn get_int_array_value(&self, var: SaveDataVar, index: usize) -> Result<i64, SaveError> {
+        let var_data = self.var(var)?;
+        get_int_array_value_from_save_data(
+            self.data,
+            var_data.slot_base_add,
+            var_data.offset,
+            &var_data.int_type,
+            index,
+        )
+    }
+
+    pub fn get_float(&self, var: SaveDataVar) -> Result<f32, SaveError> {
+        let var_data = self.var(var)?;
+        get_float_value_from_save_data(
+            self.data,
+            var_data.slot_base_add,
+            var_data.offset,
+            &var_data.int_type,
+        )
+    }
+
+    pub fn get_figure_info(
+        &self,
+        var: SaveDataVar,
+        index: usize,
+    ) -> Result<SFigureDisplayInfo, SaveError> {
+        let var_data = self.var(var)?;
+        get_figure_info_value_from_save_data(
+            self.data,
+            var_data.slot_base_add,
+            var_data.offset,
+            &var_data.int_type,
+            index,
+        )
+    }
+
+    /// The last save date, fails if the text isn't a date in the game's format.
+    pub fn get_date(&self) -> Result<NaiveDateTime, SaveError> {
+        let var_data = self.var(SaveDataVar::SaveDate)?;
+        parse_save_date(
+            &self.get_text(SaveDataVar::SaveDate)?,
+            (var_data.slot_base_add + var_data.offset) as usize,
+        )
+    }
+
+    pub fn get_text(&self, var: SaveDataVar) -> Result<String, SaveError> {
+        let var_data = self.var(var)?;
+        get_text_value_from_save_data(
+            self.data,
+            var_data.slot_base_add,
+            var_data.offset,
+            &var_data.int_type,
+        )
+    }
+
+    /// The raw bytes of the whole slot.
+    pub fn bytes(&self) -> Result<&'a [u8], SaveError> {
+        get_save_data_bytes(
+            self.data,
+            get_save_slot_base_add(self.slot)? as usize,
+            SAVE_SLOT_SIZE as usize,
+        )
+    }
+
+    /// Decodes every variable of the slot at once.
+    pub fn data(&self) -> Result<SlotData, SaveError> {
+        SlotData::decode(self.bytes()?)
+    }
+}
+
+impl<'a> SaveSlotMut<'a> {
+    pub fn as_slot(&self) -> SaveSlot<'_> {
+        SaveSlot {
+            data: self.data,
+            slot: self.slot,
+        }
+    }
+
+    /// Fails if the variable doesn't exist in the version of the save.
+    fn check_version(&self, var: SaveDataVar) -> Result<(), SaveError> {
+        if var.min_version() > self.version {
+            return Err(SaveError::NotInVersion {
+                var,
+                version: self.version,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn set_int(&mut self, var: SaveDataVar, value: i64) -> Result<(), SaveError> {
+        self.check_version(var)?;
+        let var_data = self.as_slot().var(var)?;
+        modify_save_data(
+            self.data,
+            var_data.slot_base_add,
+            var_data.offset,
+            var_data.int_type,
+            value,
+        )
+    }
+
+    pub fn set_float(&mut self, var: SaveDataVar, value: f32) -> Result<(), SaveError> {
+        self.check_version(var)?;
+        let var_data = self.as_slot().var(var)?;
+        if !matches!(var_data.int_type, SaveDataIntType::F32) {
+            return Err(SaveError::UnsupportedType {
+                address: (var_data.slot_base_add + var_data.offset) as usize,
+                int_type: var_data.int_type,
+            });
+        }
+        modify_save_data_float(self.data, var_data.slot_base_add, var_data.offset, value)
+    }
+
+    pub fn set_figure_info(
+        &mut self,
+        var: SaveDataVar,
+        index: usize,
+        figure_info: SFigureDisplayInfo,
+    ) -> Result<(), SaveError> {
+        self.check_version(var)?;
+        let var_data = self.as_slot().var(var)?;
+        modify_figure_info(
+            self.data,
+            var_data.slot_base_add,
+            var_data.offset,
+            var_data.int_type,
+            index,
+            figure_info,
+        )
+    }
+
+    pub fn set_date(&mut self, date: &NaiveDateTime) -> Result<(), SaveError> {
+        let var_data = get_save_file_variable(SaveDataVar::SaveDate, self.slot)?;
+        modify_save_data_text(
+            self.data,
+            var_data.slot_base_add,
+            var_data.offset,
+            var_data.int_type,
+            &format_save_date(date),
+        )
+    }
+
+    /// Writes back a slot decoded with `SaveSlot::data`.
+    /// Fails without writing anything if it changes variables that the save's version doesn't have.
+    pub fn set_data(&mut self, slot_data: &SlotData) -> Result<(), SaveError> {
+        let encoded = slot_data.encode()?;
+        let slot_bytes = get_save_data_bytes_mut(
+            self.data,
+            get_save_slot_base_add(self.slot)? as usize,
+            SAVE_SLOT_SIZE as usize,
+        )?;
+        check_slot_version(self.version, slot_bytes, &encoded)?;
+        slot_bytes.copy_from_slice(&encoded);
+        Ok(())
+    }
+
+    /// Overwrites the slot with the data of a brand new save.
+    /// The new save comes from the Sonic update, in older saves its variables are left as 0.
+    pub fn create(&mut self) -> Result<(), SaveError> {
+        let start_add = get_save_slot_base_add(self.slot)? as usize;
+        let mut new_save_file = get_new_save_file();
+        if self.version < SaveVersion::SonicUpdate {
+            let sonic_range = sonic_update_range();
+            new_save_file[sonic_range.start as usize..sonic_range.end as usize].fill(0);
+        }
+        get_save_data_bytes_mut(self.data, start_add, new_save_file.len())?
+            .copy_from_slice(&new_save_file);
+        Ok(())
+    }
+
+    /// Marks the slot as empty, the game ignores the rest of the data after this.
+    pub fn delete(&mut self) -> Result<(), SaveError> {
+        self.set_int(SaveDataVar::FileExists, 0)
+    }
+}
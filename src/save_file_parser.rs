@@ -1,21 +1,18 @@
-use enum_iterator::all;
 use std::{
-    env,
-    ffi::OsStr,
-    fs,
+    env, fs,
+    io::Write,
     path::{Path, PathBuf},
     str::from_utf8,
 };
-use walkdir::WalkDir;
 
 use crate::{
     EXPECTED_SAVE_FILE_SIZE,
-    save_data_info::{SaveDataIntType, SaveDataVar, SaveFileData, get_save_slot_base_add},
+    error::SaveError,
+    save_data_info::{SAVE_FIELDS, SaveDataIntType, SaveDataVar, SaveFileData},
+    steam::{REPAC_APP_ID, find_repac_prefixes_linux},
 };
 
-const LEVELS_COUNT: u32 = 40;
-const MAZES_COUNT: u32 = 15;
-
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub struct SFigureDisplayInfo {
     pub figure_id: i32,
     pub angle: f32,
@@ -26,47 +23,122 @@ enum PathType {
     Linux,
 }
 
-fn get_file_path() -> Result<String, String> {
+/// A save file found where the game saves, with where it was found for the user to tell them
+/// apart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaveLocation {
+    pub path: PathBuf,
+    pub source: String,
+    /// Name of the account folder the save is in, the Steam user id.
+    pub account: String,
+}
+
+/// Every save file of the game that can be found, the first one is the one used by default.
+pub fn find_save_files() -> Result<Vec<SaveLocation>, SaveError> {
     let pac_save_local_app_data_win = env::var("LOCALAPPDATA");
     match pac_save_local_app_data_win {
-        Ok(local_app_data_path_str) => get_final_path(local_app_data_path_str, PathType::Windows),
+        Ok(local_app_data_path_str) => {
+            let paths = get_final_paths(local_app_data_path_str, PathType::Windows)?;
+            Ok(paths
+                .into_iter()
+                .map(|(account, path)| SaveLocation {
+                    path,
+                    source: "Windows".to_string(),
+                    account,
+                })
+                .collect())
+        }
         Err(var_err) => {
-            eprintln!("Error in local data path value: {}", var_err);
-            println!("Trying linux path now");
-            // try linux path now
-            let pac_save_path_linux = find_steam_install_folder_linux();
-            match pac_save_path_linux {
-                Some(steam_path) => {
-                    let home_to_string = steam_path.to_str();
-                    match home_to_string {
-                        Some(home_str) => get_final_path(home_str.to_string(), PathType::Linux),
-                        None => Err(String::from(
-                            "Could not parse the linux OS string to String!",
-                        )),
+            eprintln!("No local data path ({}), trying linux path now", var_err);
+            // try linux path now, every steam install with the game can have its own save
+            let mut locations = vec![];
+            let mut first_error = None;
+            for prefix in find_repac_prefixes_linux()? {
+                let Some(steamapps) = prefix.steamapps.to_str() else {
+                    first_error.get_or_insert(SaveError::PathNotFound(format!(
+                        "the steam path {} is not valid unicode",
+                        prefix.steamapps.display()
+                    )));
+                    continue;
+                };
+                match get_final_paths(steamapps.to_string(), PathType::Linux) {
+                    Ok(paths) => {
+                        locations.extend(paths.into_iter().map(|(account, path)| SaveLocation {
+                            path,
+                            source: prefix.kind.name().to_string(),
+                            account,
+                        }))
+                    }
+                    Err(e) => {
+                        first_error.get_or_insert(e);
                     }
                 }
-                None => Err("<steamapps> folder could not be found!".to_string()),
+            }
+            match (locations.is_empty(), first_error) {
+                (true, Some(e)) => Err(e),
+                _ => Ok(locations),
             }
         }
     }
 }
 
-fn find_steam_install_folder_linux() -> Option<PathBuf> {
-    let home_dir = dirs::home_dir()?;
-    println!("Starting search in: {:?}", home_dir);
-
-    for entry in WalkDir::new(home_dir).into_iter().filter_map(|e| e.ok()) {
-        let path = entry.path();
-
-        if path.is_dir() && path.file_name() == Some(OsStr::new("steamapps")) {
-            return Some(path.to_path_buf());
+/// Finds the save file of the account, or the only one found when there's no account.
+/// Fails if there are saves of several accounts and none was picked, to not edit someone else's
+/// save by mistake.
+pub fn find_account_save_file(account: Option<&str>) -> Result<SaveLocation, SaveError> {
+    let locations = find_save_files()?;
+    match account {
+        Some(account) => locations
+            .into_iter()
+            .find(|location| location.account == account)
+            .ok_or_else(|| {
+                SaveError::PathNotFound(format!("no save file for the account {}", account))
+            }),
+        None => {
+            let mut accounts: Vec<&str> = locations
+                .iter()
+                .map(|location| location.account.as_str())
+                .collect();
+            accounts.sort();
+            accounts.dedup();
+            if accounts.len() > 1 {
+                return Err(SaveError::PathNotFound(format!(
+                    "there are saves of several accounts ({}), pick one",
+                    accounts.join(", ")
+                )));
+            }
+            locations
+                .into_iter()
+                .next()
+                .ok_or_else(|| SaveError::PathNotFound("no save file found".to_string()))
         }
     }
+}
 
-    None
+/// Environment variable with the path of a save file to use instead of looking for the game's.
+pub const SAVE_PATH_ENV_VAR: &str = "PW2REPAC_SAVE_PATH";
+
+/// Path given in `SAVE_PATH_ENV_VAR`, if it's set.
+pub fn get_env_file_path() -> Option<PathBuf> {
+    env::var_os(SAVE_PATH_ENV_VAR)
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
 }
 
-fn get_final_path(base_variable_path: String, path_type: PathType) -> Result<String, String> {
+/// Path of the save file used by default: the one in `SAVE_PATH_ENV_VAR`, or the game's save
+/// found by `find_account_save_file`.
+pub fn get_file_path() -> Result<PathBuf, SaveError> {
+    if let Some(path) = get_env_file_path() {
+        return Ok(path);
+    }
+    find_account_save_file(None).map(|location| location.path)
+}
+
+/// Every `SaveGames/<account id>/DAT00000.dat` file, sorted by account id.
+fn get_final_paths(
+    base_variable_path: String,
+    path_type: PathType,
+) -> Result<Vec<(String, PathBuf)>, SaveError> {
     let local_data_path = Path::new(&base_variable_path);
     let save_games_path = match path_type {
         PathType::Windows => local_data_path
@@ -76,7 +148,7 @@ fn get_final_path(base_variable_path: String, path_type: PathType) -> Result<Str
             .join("SaveGames"),
         PathType::Linux => local_data_path
             .join("compatdata")
-            .join("2324290")
+            .join(REPAC_APP_ID)
             .join("pfx")
             .join("drive_c")
             .join("users")
@@ -89,224 +161,382 @@ fn get_final_path(base_variable_path: String, path_type: PathType) -> Result<Str
             .join("SaveGames"),
     };
 
-    println!(
-        "Reading path: {}",
-        save_games_path.to_str().unwrap_or_default()
-    );
-    let mut save_file_path = String::default();
-    match fs::read_dir(save_games_path) {
-        Ok(entries) => {
-            // just assume there's 1 folder so "last" element will be in the path
-            for entry_res in entries {
-                match entry_res {
-                    Ok(entry) => {
-                        save_file_path = entry
-                            .path()
-                            .join("DAT00000.dat")
-                            .to_str()
-                            .unwrap_or_default()
-                            .to_string();
-                    }
-                    Err(e) => eprintln!("Error reading save directory entry: {}", e),
-                }
-            }
-        }
-        Err(e) => {
-            return Err(format!("Error when reading SaveGames path: {}", e));
+    eprintln!("Reading path: {}", save_games_path.display());
+    let entries = fs::read_dir(&save_games_path).map_err(|source| SaveError::Io {
+        path: save_games_path.clone(),
+        source,
+    })?;
+
+    // there's a folder for each account that played the game on this computer
+    let mut save_file_paths = vec![];
+    for entry_res in entries {
+        let entry = entry_res.map_err(|source| SaveError::Io {
+            path: save_games_path.clone(),
+            source,
+        })?;
+        let save_file_path = entry.path().join("DAT00000.dat");
+        if save_file_path.is_file() {
+            let account = entry.file_name().to_string_lossy().to_string();
+            save_file_paths.push((account, save_file_path));
         }
     }
+    save_file_paths.sort();
+
+    if save_file_paths.is_empty() {
+        return Err(SaveError::PathNotFound(format!(
+            "{} has no save folders",
+            save_games_path.display()
+        )));
+    }
+    for (_, path) in &save_file_paths {
+        eprintln!("Found save file: {}", path.display());
+    }
+    Ok(save_file_paths)
+}
 
-    println!("Final Path is: {}", save_file_path);
-    Ok(save_file_path)
+pub fn read_save_file(path: &Path) -> Result<Vec<u8>, SaveError> {
+    let save_file_bytes = fs::read(path).map_err(|source| SaveError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    check_save_file_size(&save_file_bytes)?;
+    Ok(save_file_bytes)
 }
 
-pub fn read_save_file() -> Result<Vec<u8>, String> {
-    let pac_save_path = get_file_path();
+/// Writes the save file without ever leaving a half written file at `path`: the bytes go to a
+/// temporary file in the same folder, which is synced to disk and then renamed over the save.
+/// The permissions of the old file are kept, and the new one is read back to check it.
+pub fn write_save_file(path: &Path, save_data: &[u8]) -> Result<(), SaveError> {
+    check_save_file_size(save_data)?;
 
-    match pac_save_path {
-        Ok(path) => {
-            let save_file_bytes = fs::read(path);
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| SaveError::PathNotFound(format!("{} is not a file path", path.display())))?;
+    let temp_path = path.with_file_name(format!(
+        ".{}.{}.tmp",
+        file_name.to_string_lossy(),
+        std::process::id()
+    ));
 
-            match save_file_bytes {
-                Ok(vec) => Ok(vec),
-                Err(err) => Err(format!("Error when reading bytes from save file: {}", err)),
-            }
-        }
-        Err(e) => Err(e),
+    if let Err(source) = write_temp_file(&temp_path, path, save_data) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(SaveError::Io {
+            path: temp_path,
+            source,
+        });
+    }
+    if let Err(source) = fs::rename(&temp_path, path) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(SaveError::Io {
+            path: path.to_path_buf(),
+            source,
+        });
+    }
+    sync_parent_folder(path);
+
+    let written = fs::read(path).map_err(|source| SaveError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    if written != save_data {
+        return Err(SaveError::WriteNotVerified {
+            path: path.to_path_buf(),
+        });
+    }
+    Ok(())
+}
+
+/// Writes and syncs the temporary file, with the permissions of the file it will replace.
+fn write_temp_file(temp_path: &Path, path: &Path, save_data: &[u8]) -> std::io::Result<()> {
+    let mut temp_file = fs::File::create(temp_path)?;
+    temp_file.write_all(save_data)?;
+    if let Ok(metadata) = fs::metadata(path) {
+        temp_file.set_permissions(metadata.permissions())?;
+    }
+    temp_file.sync_all()
+}
+
+/// Makes the rename itself survive a crash, folders can only be synced like this on Unix.
+#[cfg(unix)]
+fn sync_parent_folder(path: &Path) {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    if let Ok(folder) = fs::File::open(parent) {
+        let _ = folder.sync_all();
     }
 }
 
-pub fn write_save_file(save_data: Vec<u8>) -> std::io::Result<()> {
-    let pac_save_path = get_file_path();
+#[cfg(not(unix))]
+fn sync_parent_folder(_path: &Path) {}
 
-    match pac_save_path {
-        Ok(path) => {
-            fs::write(path, &save_data)?;
-            Ok(())
-        }
-        Err(e) => {
-            println!("{}", e);
-            Ok(())
-        }
+/// FNV-1a hash of some bytes, it's the same in every build and every platform.
+pub fn fnv1a_hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+pub fn check_save_file_size(save_data: &[u8]) -> Result<(), SaveError> {
+    if save_data.len() != EXPECTED_SAVE_FILE_SIZE {
+        return Err(SaveError::SizeMismatch {
+            expected: EXPECTED_SAVE_FILE_SIZE,
+            found: save_data.len(),
+        });
     }
+    Ok(())
+}
+
+/// Returns the `len` bytes at `address`, or an error if they go past the end of the save data.
+pub fn get_save_data_bytes(
+    save_data: &[u8],
+    address: usize,
+    len: usize,
+) -> Result<&[u8], SaveError> {
+    address
+        .checked_add(len)
+        .and_then(|end| save_data.get(address..end))
+        .ok_or(SaveError::OutOfRange {
+            address,
+            len,
+            data_size: save_data.len(),
+        })
+}
+
+pub fn get_save_data_bytes_mut(
+    save_data: &mut [u8],
+    address: usize,
+    len: usize,
+) -> Result<&mut [u8], SaveError> {
+    let data_size = save_data.len();
+    address
+        .checked_add(len)
+        .and_then(|end| save_data.get_mut(address..end))
+        .ok_or(SaveError::OutOfRange {
+            address,
+            len,
+            data_size,
+        })
+}
+
+fn read_4_bytes(save_data: &[u8], address: usize) -> Result<[u8; 4], SaveError> {
+    let bytes = get_save_data_bytes(save_data, address, 4)?;
+    Ok([bytes[0], bytes[1], bytes[2], bytes[3]])
 }
 
 pub fn get_int_value_from_save_data(
-    save_file_raw: Vec<u8>,
+    save_data: &[u8],
     slot_base: u32,
     offset: u32,
     int_type: &SaveDataIntType,
-) -> i64 {
-    if save_file_raw.len() < EXPECTED_SAVE_FILE_SIZE {
-        return 0;
-    }
+) -> Result<i64, SaveError> {
+    let address = slot_base as usize + offset as usize;
     match int_type {
-        SaveDataIntType::Bool => save_file_raw[slot_base as usize + offset as usize].into(),
-        SaveDataIntType::U32 => {
-            let save_data_slice: &[u8] = &save_file_raw[(slot_base as usize + offset as usize)
-                ..(slot_base as usize + offset as usize + 4)];
-            let value_raw = u32::from_le_bytes(save_data_slice.try_into().unwrap_or_default());
-            value_raw.into()
-        }
-        SaveDataIntType::I32 => {
-            let save_data_slice: &[u8] = &save_file_raw[(slot_base as usize + offset as usize)
-                ..(slot_base as usize + offset as usize + 4)];
-            let value_raw = i32::from_le_bytes(save_data_slice.try_into().unwrap_or_default());
-            value_raw.into()
+        SaveDataIntType::Bool => Ok(get_save_data_bytes(save_data, address, 1)?[0].into()),
+        SaveDataIntType::U32 | SaveDataIntType::Bitfield => {
+            Ok(u32::from_le_bytes(read_4_bytes(save_data, address)?).into())
         }
-        SaveDataIntType::Arrayi32(_)
+        SaveDataIntType::I32 => Ok(i32::from_le_bytes(read_4_bytes(save_data, address)?).into()),
+        SaveDataIntType::F32
+        | SaveDataIntType::Arrayi32(_)
         | SaveDataIntType::ArrayText(_)
         | SaveDataIntType::Arrayu8(_)
-        | SaveDataIntType::SFigureDisplayInfoArray(_) => {
-            println!("Wrong function for this... offset: {:X}", offset);
-            0
-        }
+        | SaveDataIntType::SFigureDisplayInfoArray(_)
+        | SaveDataIntType::BitfieldArray(_) => Err(SaveError::UnsupportedType {
+            address,
+            int_type: *int_type,
+        }),
     }
 }
 
 pub fn get_int_array_from_save_data(
-    save_file_raw: Vec<u8>,
+    save_data: &[u8],
     slot_base: u32,
     offset: u32,
     int_type: &SaveDataIntType,
-) -> Vec<i64> {
-    if save_file_raw.len() < EXPECTED_SAVE_FILE_SIZE {
-        return vec![];
-    }
+) -> Result<Vec<i64>, SaveError> {
+    let address = slot_base as usize + offset as usize;
     match int_type {
         SaveDataIntType::Arrayi32(len) => {
-            let mut final_vec: Vec<i64> = vec![];
-            for i in 0..(*len as usize) {
-                let save_data_slice: &[u8] =
-                    &save_file_raw[(slot_base as usize + offset as usize + (i * 4))
-                        ..(slot_base as usize + offset as usize + 4 + (i * 4))];
-                let value_raw = i32::from_le_bytes(save_data_slice.try_into().unwrap_or_default());
-                final_vec.push(value_raw as i64);
-            }
-            final_vec
+            let save_data_slice = get_save_data_bytes(save_data, address, *len as usize * 4)?;
+            Ok(save_data_slice
+                .chunks_exact(4)
+                .map(|bytes| i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as i64)
+                .collect())
         }
-        SaveDataIntType::Arrayu8(len) => {
-            let mut final_vec: Vec<i64> = vec![];
-            for i in 0..(*len as usize) {
-                let byte_found: u8 = save_file_raw[slot_base as usize + offset as usize + i];
-                final_vec.push(byte_found as i64);
-            }
-            final_vec
+        SaveDataIntType::BitfieldArray(len) => {
+            let save_data_slice = get_save_data_bytes(save_data, address, *len as usize * 4)?;
+            Ok(save_data_slice
+                .chunks_exact(4)
+                .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as i64)
+                .collect())
         }
-        SaveDataIntType::ArrayText(_) => {
-            println!("Text not supported!");
-            vec![]
+        SaveDataIntType::Arrayu8(len) => {
+            let save_data_slice = get_save_data_bytes(save_data, address, *len as usize)?;
+            Ok(save_data_slice.iter().map(|byte| *byte as i64).collect())
         }
-        SaveDataIntType::Bool
+        SaveDataIntType::ArrayText(_)
+        | SaveDataIntType::Bool
         | SaveDataIntType::U32
         | SaveDataIntType::I32
-        | SaveDataIntType::SFigureDisplayInfoArray(_) => {
-            println!("This isn't an array!");
-            vec![]
+        | SaveDataIntType::F32
+        | SaveDataIntType::SFigureDisplayInfoArray(_)
+        | SaveDataIntType::Bitfield => Err(SaveError::UnsupportedType {
+            address,
+            int_type: *int_type,
+        }),
+    }
+}
+
+/// Reads a single element of an array, without reading the rest of it.
+pub fn get_int_array_value_from_save_data(
+    save_data: &[u8],
+    slot_base: u32,
+    offset: u32,
+    int_type: &SaveDataIntType,
+    index: usize,
+) -> Result<i64, SaveError> {
+    let address = slot_base as usize + offset as usize;
+    let (len, element_size) = match int_type {
+        SaveDataIntType::Arrayi32(len) | SaveDataIntType::BitfieldArray(len) => (*len as usize, 4),
+        SaveDataIntType::Arrayu8(len) => (*len as usize, 1),
+        _ => {
+            return Err(SaveError::UnsupportedType {
+                address,
+                int_type: *int_type,
+            });
         }
+    };
+    let element_address = address + index * element_size;
+    if index >= len {
+        return Err(SaveError::InvalidValue {
+            address: element_address,
+            reason: format!("index {} is out of the array of length {}", index, len),
+        });
+    }
+    match int_type {
+        SaveDataIntType::BitfieldArray(_) => {
+            Ok(u32::from_le_bytes(read_4_bytes(save_data, element_address)?).into())
+        }
+        SaveDataIntType::Arrayi32(_) => {
+            Ok(i32::from_le_bytes(read_4_bytes(save_data, element_address)?).into())
+        }
+        _ => Ok(get_save_data_bytes(save_data, element_address, 1)?[0].into()),
     }
 }
 
 pub fn get_figure_info_from_save_data(
-    save_file_raw: Vec<u8>,
+    save_data: &[u8],
     slot_base: u32,
     offset: u32,
     len: u32,
-) -> Vec<SFigureDisplayInfo> {
-    let mut final_vec: Vec<SFigureDisplayInfo> = vec![];
-    for i in 0..(len as usize) {
-        let save_data_slice_id: &[u8] =
-            &save_file_raw[(slot_base as usize + offset as usize + (i * 8))
-                ..(slot_base as usize + offset as usize + 4 + (i * 8))];
-        let figure_id = i32::from_le_bytes(save_data_slice_id.try_into().unwrap_or_default());
-        let save_data_slice_angle: &[u8] =
-            &save_file_raw[(slot_base as usize + offset as usize + 4 + (i * 8))
-                ..(slot_base as usize + offset as usize + 8 + (i * 8))];
-        let angle = f32::from_le_bytes(save_data_slice_angle.try_into().unwrap_or_default());
+) -> Result<Vec<SFigureDisplayInfo>, SaveError> {
+    let address = slot_base as usize + offset as usize;
+    let save_data_slice = get_save_data_bytes(save_data, address, len as usize * 8)?;
+    Ok(save_data_slice
+        .chunks_exact(8)
+        .map(|bytes| SFigureDisplayInfo {
+            figure_id: i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            angle: f32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]),
+        })
+        .collect())
+}
 
-        final_vec.push(SFigureDisplayInfo { figure_id, angle });
+/// Address of the figure info at `index`, checking that the array has that many elements.
+fn figure_info_address(
+    slot_base: u32,
+    offset: u32,
+    int_type: &SaveDataIntType,
+    index: usize,
+) -> Result<usize, SaveError> {
+    let address = slot_base as usize + offset as usize;
+    match int_type {
+        SaveDataIntType::SFigureDisplayInfoArray(len) => {
+            let element_address = address + index * 8;
+            if index >= *len as usize {
+                return Err(SaveError::InvalidValue {
+                    address: element_address,
+                    reason: format!("index {} is out of the array of length {}", index, len),
+                });
+            }
+            Ok(element_address)
+        }
+        _ => Err(SaveError::UnsupportedType {
+            address,
+            int_type: *int_type,
+        }),
+    }
+}
+
+pub fn get_figure_info_value_from_save_data(
+    save_data: &[u8],
+    slot_base: u32,
+    offset: u32,
+    int_type: &SaveDataIntType,
+    index: usize,
+) -> Result<SFigureDisplayInfo, SaveError> {
+    let address = figure_info_address(slot_base, offset, int_type, index)?;
+    Ok(SFigureDisplayInfo {
+        figure_id: i32::from_le_bytes(read_4_bytes(save_data, address)?),
+        angle: f32::from_le_bytes(read_4_bytes(save_data, address + 4)?),
+    })
+}
+
+pub fn get_float_value_from_save_data(
+    save_data: &[u8],
+    slot_base: u32,
+    offset: u32,
+    int_type: &SaveDataIntType,
+) -> Result<f32, SaveError> {
+    let address = slot_base as usize + offset as usize;
+    match int_type {
+        SaveDataIntType::F32 => Ok(f32::from_le_bytes(read_4_bytes(save_data, address)?)),
+        _ => Err(SaveError::UnsupportedType {
+            address,
+            int_type: *int_type,
+        }),
     }
-    final_vec
 }
 
 pub fn get_text_value_from_save_data(
-    save_file_raw: Vec<u8>,
+    save_data: &[u8],
     slot_base: u32,
     offset: u32,
     int_type: &SaveDataIntType,
-) -> String {
+) -> Result<String, SaveError> {
+    let address = slot_base as usize + offset as usize;
     match int_type {
         SaveDataIntType::ArrayText(len) => {
-            let save_data_slice: &[u8] = &save_file_raw[(slot_base as usize + offset as usize)
-                ..(slot_base as usize + offset as usize + *len as usize)];
+            let save_data_slice = get_save_data_bytes(save_data, address, *len as usize)?;
             match from_utf8(save_data_slice) {
-                Ok(str) => str.to_string(),
-                Err(_) => String::from("Error"),
+                Ok(str) => Ok(str.to_string()),
+                Err(e) => Err(SaveError::InvalidValue {
+                    address,
+                    reason: format!("text is not valid UTF-8 ({})", e),
+                }),
             }
         }
-        _ => {
-            println!("This isn't text!");
-            String::from("Error")
-        }
+        _ => Err(SaveError::UnsupportedType {
+            address,
+            int_type: *int_type,
+        }),
     }
 }
 
-pub fn get_all_save_file_vars(slot: u8) -> Vec<SaveFileData> {
-    let all_vars_iter = all::<SaveDataVar>();
-    let mut all_vars = vec![];
-    for var in all_vars_iter {
-        all_vars.push(get_save_file_variable(var, slot));
-    }
-    all_vars
+pub fn get_all_save_file_vars(slot: u8) -> Result<Vec<SaveFileData>, SaveError> {
+    SAVE_FIELDS
+        .iter()
+        .map(|field| field.at_slot(slot))
+        .collect()
 }
 
-pub fn get_basic_save_file_vars(slot: u8) -> Vec<SaveFileData> {
-    vec![
-        get_save_file_variable(SaveDataVar::PlayTimeHours, slot),
-        get_save_file_variable(SaveDataVar::PlayTimeMinutes, slot),
-        get_save_file_variable(SaveDataVar::PlayTimeSeconds, slot),
-        get_save_file_variable(SaveDataVar::ScoreList, slot),
-        get_save_file_variable(SaveDataVar::TimeTrialList, slot),
-        get_save_file_variable(SaveDataVar::StageMazeFlagList, slot),
-        get_save_file_variable(SaveDataVar::MazesScoreList, slot),
-        get_save_file_variable(SaveDataVar::Lives, slot),
-        get_save_file_variable(SaveDataVar::MagicKeyUnlocked, slot),
-        get_save_file_variable(SaveDataVar::JukeBoxBGM, slot),
-        get_save_file_variable(SaveDataVar::JukeBoxBGMCollab, slot),
-        get_save_file_variable(SaveDataVar::MedalNum, slot),
-        get_save_file_variable(SaveDataVar::CameraSpeedX, slot),
-        get_save_file_variable(SaveDataVar::CameraSpeedY, slot),
-        get_save_file_variable(SaveDataVar::CameraControlX, slot),
-        get_save_file_variable(SaveDataVar::CameraControlY, slot),
-        get_save_file_variable(SaveDataVar::CameraAssistFlag, slot),
-        get_save_file_variable(SaveDataVar::CameraYAutoRotateFlag, slot),
-        get_save_file_variable(SaveDataVar::KeyConfigP1, slot),
-        get_save_file_variable(SaveDataVar::PlayerSkinId, slot),
-        get_save_file_variable(SaveDataVar::PlayerSkinIdCollab, slot),
-        get_save_file_variable(SaveDataVar::OriginalHighScore, slot),
-        get_save_file_variable(SaveDataVar::PacmaniaHighScore, slot),
-        get_save_file_variable(SaveDataVar::PacAttackHighScore, slot),
-    ]
+pub fn get_basic_save_file_vars(slot: u8) -> Result<Vec<SaveFileData>, SaveError> {
+    SAVE_FIELDS
+        .iter()
+        .filter(|field| field.basic)
+        .map(|field| field.at_slot(slot))
+        .collect()
 }
 
 pub fn modify_save_data(
@@ -315,954 +545,107 @@ pub fn modify_save_data(
     offset: u32,
     int_type: SaveDataIntType,
     value_to_write: i64,
-) {
-    let pos_to_write = (slot_base_add + offset) as usize;
+) -> Result<(), SaveError> {
+    let pos_to_write = slot_base_add as usize + offset as usize;
+    let out_of_type_range = |type_name: &str| SaveError::InvalidValue {
+        address: pos_to_write,
+        reason: format!("{} doesn't fit in {}", value_to_write, type_name),
+    };
 
     match int_type {
         SaveDataIntType::Bool => {
             let value_to_bool: u8 = if value_to_write > 0 { 1 } else { 0 };
-            save_data[pos_to_write] = value_to_bool;
+            get_save_data_bytes_mut(save_data, pos_to_write, 1)?[0] = value_to_bool;
         }
-        SaveDataIntType::U32 => {
-            let value_to_bytes: [u8; 4] = (value_to_write as u32).to_le_bytes();
-            for (i, byte) in value_to_bytes.iter().enumerate() {
-                save_data[pos_to_write + i] = *byte;
-            }
+        SaveDataIntType::U32 | SaveDataIntType::Bitfield | SaveDataIntType::BitfieldArray(_) => {
+            let value: u32 = value_to_write
+                .try_into()
+                .map_err(|_| out_of_type_range("u32"))?;
+            get_save_data_bytes_mut(save_data, pos_to_write, 4)?
+                .copy_from_slice(&value.to_le_bytes());
         }
-        SaveDataIntType::I32
-        | SaveDataIntType::Arrayi32(_)
-        | SaveDataIntType::SFigureDisplayInfoArray(_) => {
-            let value_to_bytes: [u8; 4] = (value_to_write as i32).to_le_bytes();
-            for (i, byte) in value_to_bytes.iter().enumerate() {
-                save_data[pos_to_write + i] = *byte;
-            }
+        SaveDataIntType::I32 | SaveDataIntType::Arrayi32(_) => {
+            let value: i32 = value_to_write
+                .try_into()
+                .map_err(|_| out_of_type_range("i32"))?;
+            get_save_data_bytes_mut(save_data, pos_to_write, 4)?
+                .copy_from_slice(&value.to_le_bytes());
         }
         SaveDataIntType::Arrayu8(_) => {
-            let value_to_u8 = value_to_write as u8;
-            save_data[pos_to_write] = value_to_u8;
+            let value: u8 = value_to_write
+                .try_into()
+                .map_err(|_| out_of_type_range("u8"))?;
+            get_save_data_bytes_mut(save_data, pos_to_write, 1)?[0] = value;
         }
-        SaveDataIntType::ArrayText(_) => {
-            // meh not worth it
+        // these aren't integers, they have their own writers, text is written with
+        // `modify_save_data_text`
+        SaveDataIntType::F32
+        | SaveDataIntType::ArrayText(_)
+        | SaveDataIntType::SFigureDisplayInfoArray(_) => {
+            return Err(SaveError::UnsupportedType {
+                address: pos_to_write,
+                int_type,
+            });
         }
     };
+    Ok(())
 }
 
-pub fn modify_save_data_float(
+/// Writes both the id and the angle of the figure info at `index` of the array.
+pub fn modify_figure_info(
     save_data: &mut [u8],
     slot_base_add: u32,
     offset: u32,
-    value_to_write: f32,
-) {
-    let pos_to_write = (slot_base_add + offset) as usize;
-    let value_to_bytes: [u8; 4] = (value_to_write).to_le_bytes();
-    for (i, byte) in value_to_bytes.iter().enumerate() {
-        save_data[pos_to_write + i] = *byte;
+    int_type: SaveDataIntType,
+    index: usize,
+    figure_info: SFigureDisplayInfo,
+) -> Result<(), SaveError> {
+    let pos_to_write = figure_info_address(slot_base_add, offset, &int_type, index)?;
+    let bytes = get_save_data_bytes_mut(save_data, pos_to_write, 8)?;
+    bytes[0..4].copy_from_slice(&figure_info.figure_id.to_le_bytes());
+    bytes[4..8].copy_from_slice(&figure_info.angle.to_le_bytes());
+    Ok(())
+}
+
+/// Writes `text` into a text array, the bytes after it are filled with zeros.
+pub fn modify_save_data_text(
+    save_data: &mut [u8],
+    slot_base_add: u32,
+    offset: u32,
+    int_type: SaveDataIntType,
+    text: &str,
+) -> Result<(), SaveError> {
+    let pos_to_write = slot_base_add as usize + offset as usize;
+    let SaveDataIntType::ArrayText(len) = int_type else {
+        return Err(SaveError::UnsupportedType {
+            address: pos_to_write,
+            int_type,
+        });
+    };
+    if text.len() > len as usize {
+        return Err(SaveError::InvalidValue {
+            address: pos_to_write,
+            reason: format!("\"{}\" is longer than {} bytes", text, len),
+        });
     }
+    let bytes = get_save_data_bytes_mut(save_data, pos_to_write, len as usize)?;
+    bytes.fill(0);
+    bytes[..text.len()].copy_from_slice(text.as_bytes());
+    Ok(())
 }
 
-pub fn get_save_file_variable(req_data: SaveDataVar, slot: u8) -> SaveFileData {
-    let slot_base_add: u32 = get_save_slot_base_add(slot);
+pub fn modify_save_data_float(
+    save_data: &mut [u8],
+    slot_base_add: u32,
+    offset: u32,
+    value_to_write: f32,
+) -> Result<(), SaveError> {
+    let pos_to_write = slot_base_add as usize + offset as usize;
+    get_save_data_bytes_mut(save_data, pos_to_write, 4)?
+        .copy_from_slice(&value_to_write.to_le_bytes());
+    Ok(())
+}
 
-    match req_data {
-        SaveDataVar::FileExists => SaveFileData {
-            variable_name: "m_bExist".into(),
-            variable_name_simple: "File Exists".into(),
-            offset: 0x0,
-            int_type: SaveDataIntType::Bool,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::SaveDate => SaveFileData {
-            variable_name: "m_bDateList".into(),
-            variable_name_simple: "Last Save Date".into(),
-            offset: 0x18,
-            int_type: SaveDataIntType::ArrayText(16),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::PlayTimeHours => SaveFileData {
-            variable_name: "m_iPlayHours".into(),
-            variable_name_simple: "File Hours".into(),
-            offset: 0x28,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::PlayTimeMinutes => SaveFileData {
-            variable_name: "m_iPlayMinutes".into(),
-            variable_name_simple: "File Minutes".into(),
-            offset: 0x2C,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::PlayTimeSeconds => SaveFileData {
-            variable_name: "m_iPlaySeconds".into(),
-            variable_name_simple: "File Seconds".into(),
-            offset: 0x30,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::StageFlagList => SaveFileData {
-            variable_name: "m_bStageFlagList".into(),
-            variable_name_simple: "Stages Unlocked".into(),
-            offset: 0x34,
-            int_type: SaveDataIntType::Arrayu8(LEVELS_COUNT),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::ScoreList => SaveFileData {
-            variable_name: "m_iStageScoreList".into(),
-            variable_name_simple: "Scores List".into(),
-            offset: 0x80,
-            int_type: SaveDataIntType::Arrayi32(LEVELS_COUNT),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::TimeTrialList => SaveFileData {
-            variable_name: "m_iStageTimeList".into(),
-            variable_name_simple: "Time Trials List".into(),
-            offset: 0x1AC,
-            int_type: SaveDataIntType::Arrayi32(LEVELS_COUNT),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::TimeTrialCoopList => SaveFileData {
-            variable_name: "m_iStageTimeListCoop".into(),
-            variable_name_simple: "Time Trials Coop List".into(),
-            offset: 0x210,
-            int_type: SaveDataIntType::Arrayi32(LEVELS_COUNT),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::FriendsFlagList => SaveFileData {
-            variable_name: "m_bFriendFlagList".into(),
-            variable_name_simple: "Friends Flag List".into(),
-            offset: 0x404,
-            int_type: SaveDataIntType::Arrayu8(9),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::FriendsTalkList => SaveFileData {
-            variable_name: "m_uFriendTalkList".into(),
-            variable_name_simple: "Friends Talk List".into(),
-            offset: 0x414,
-            int_type: SaveDataIntType::Arrayi32(34), // game says u32 even tho theres clear negs
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::StageMazeFlagList => SaveFileData {
-            variable_name: "m_bStageMazeFlagList".into(),
-            variable_name_simple: "Maze Unlocked Flags".into(),
-            offset: 0x450,
-            int_type: SaveDataIntType::Arrayu8(MAZES_COUNT),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::LastStageId => SaveFileData {
-            variable_name: "m_iLastStageId".into(),
-            variable_name_simple: "Last Stage Id".into(),
-            offset: 0x464,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::LastGameModeId => SaveFileData {
-            variable_name: "m_iLastGameModeId".into(),
-            variable_name_simple: "Last Game Mode Id".into(),
-            offset: 0x468,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::Lives => SaveFileData {
-            variable_name: "m_iStockNum".into(),
-            variable_name_simple: "Lives".into(),
-            offset: 0x46C,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::MazeFlagList => SaveFileData {
-            variable_name: "m_bMazeFlagList".into(),
-            variable_name_simple: "Maze Flag List".into(),
-            offset: 0x470,
-            int_type: SaveDataIntType::Arrayu8(MAZES_COUNT),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::MazesScoreList => SaveFileData {
-            variable_name: "m_iMazeScoreList".into(),
-            variable_name_simple: "Mazes Score List".into(),
-            offset: 0x4A4,
-            int_type: SaveDataIntType::Arrayi32(MAZES_COUNT),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::FruitsGetNum => SaveFileData {
-            variable_name: "m_iFruitsGetNum".into(),
-            variable_name_simple: "Fruits Get Number".into(),
-            offset: 0x56C,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::CapsuleGetNum => SaveFileData {
-            variable_name: "m_iCapsuleGetNum".into(),
-            variable_name_simple: "Capsule Get Number".into(),
-            offset: 0x570,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::MedalGetNum => SaveFileData {
-            variable_name: "m_iMedalGetNum".into(),
-            variable_name_simple: "Medal Get Number".into(),
-            offset: 0x574,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::BombDotKillNum => SaveFileData {
-            variable_name: "m_iBombDotKillNum".into(),
-            variable_name_simple: "Bomb Dot Kill Number".into(),
-            offset: 0x578,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::DotKillNum => SaveFileData {
-            variable_name: "m_iPacDotKillNum".into(),
-            variable_name_simple: "PacDot Kill Number".into(),
-            offset: 0x57C,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::RevRollKillNum => SaveFileData {
-            variable_name: "m_iPacDashKillNum".into(),
-            variable_name_simple: "Rev Roll Kill Number".into(),
-            offset: 0x580,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::ButtBounceKillNum => SaveFileData {
-            variable_name: "m_iHipKillNum".into(),
-            variable_name_simple: "Butt Bounce Kill Number".into(),
-            offset: 0x584,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::GhostKillNum => SaveFileData {
-            variable_name: "m_iGhostKillNum".into(),
-            variable_name_simple: "Ghost Kill Number".into(),
-            offset: 0x588,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::MagicKeyUnlocked => SaveFileData {
-            variable_name: "m_bGetMagicKey".into(),
-            variable_name_simple: "Magic Key Unlocked".into(),
-            offset: 0x58C,
-            int_type: SaveDataIntType::Bool,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::TrueEnding => SaveFileData {
-            variable_name: "m_bAllTrueEnding".into(),
-            variable_name_simple: "True Ending (unused?)".into(),
-            offset: 0x590,
-            int_type: SaveDataIntType::Bool,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::MarathonUnlocked => SaveFileData {
-            variable_name: "m_bUnlockMarathon".into(),
-            variable_name_simple: "Marathon Unlocked (unused?)".into(),
-            offset: 0x594,
-            int_type: SaveDataIntType::Bool,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::MarathonHighScore => SaveFileData {
-            variable_name: "m_iMarathonHighScore".into(),
-            variable_name_simple: "Marathon High Score (unused?)".into(),
-            offset: 0x598,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::MarathonClear => SaveFileData {
-            variable_name: "m_iMarathonClearFlag".into(),
-            variable_name_simple: "Marathon Cleared (unused?)".into(),
-            offset: 0x59C,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::LastGISaveArea => SaveFileData {
-            variable_name: "m_iLastGISaveArea".into(),
-            variable_name_simple: "Last GI Save Area (unused?)".into(),
-            offset: 0x5A0,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::LoadInitScene => SaveFileData {
-            variable_name: "m_iLoadInitScene".into(),
-            variable_name_simple: "Load Init Scene".into(),
-            offset: 0x5A4,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::DLCApplyFlag => SaveFileData {
-            variable_name: "m_iDLCApplyFlag".into(),
-            variable_name_simple: "DLC Apply Flag".into(),
-            offset: 0x5A8,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::JukeBoxBGM => SaveFileData {
-            variable_name: "m_iJukeBoxBGMKind".into(),
-            variable_name_simple: "Jukebox Music".into(),
-            offset: 0x5AC,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::JukeBoxMode => SaveFileData {
-            variable_name: "m_iJukeBoxMode".into(),
-            variable_name_simple: "Jukebox Mode".into(),
-            offset: 0x5B0,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::JukeBoxLoop => SaveFileData {
-            variable_name: "m_iJukeBoxLoop".into(),
-            variable_name_simple: "Jukebox Loop".into(),
-            offset: 0x5B4,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::JukeBoxOrder => SaveFileData {
-            variable_name: "m_iJukeBoxOrder".into(),
-            variable_name_simple: "Jukebox Order".into(),
-            offset: 0x5B8,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::JukeBoxFlag => SaveFileData {
-            variable_name: "m_iJukeBoxFlag".into(),
-            variable_name_simple: "Jukebox Flag".into(),
-            offset: 0x5BC,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::JukeBoxUnlockFlagList => SaveFileData {
-            variable_name: "m_bJukeBoxUnlockFlagList".into(),
-            variable_name_simple: "Jukebox Songs Unlocked".into(),
-            offset: 0x5C2,
-            int_type: SaveDataIntType::Arrayu8(83),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::MedalNum => SaveFileData {
-            variable_name: "m_iMedalNum".into(),
-            variable_name_simple: "Medals".into(),
-            offset: 0x640,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::CapsuleFlag => SaveFileData {
-            variable_name: "m_iCapsuleFlag".into(),
-            variable_name_simple: "Capsule Flags".into(),
-            offset: 0x644,
-            int_type: SaveDataIntType::Arrayi32(150),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::StageCherryFlag => SaveFileData {
-            variable_name: "m_iStageCherryFlag".into(),
-            variable_name_simple: "Cherries Obtained Bitfield".into(),
-            offset: 0x89C,
-            int_type: SaveDataIntType::Arrayi32(35),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::StageStrawberryFlag => SaveFileData {
-            variable_name: "m_iStageStrawberryFlag".into(),
-            variable_name_simple: "Strawberries Obtained Bitfield".into(),
-            offset: 0x9C8,
-            int_type: SaveDataIntType::Arrayi32(35),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::StageOrangeFlag => SaveFileData {
-            variable_name: "m_iStageOrangeFlag".into(),
-            variable_name_simple: "Oranges Obtained Bitfield".into(),
-            offset: 0xAF4,
-            int_type: SaveDataIntType::Arrayi32(35),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::StageAppleFlag => SaveFileData {
-            variable_name: "m_iStageAppleFlag".into(),
-            variable_name_simple: "Apples Obtained Bitfield".into(),
-            offset: 0xC20,
-            int_type: SaveDataIntType::Arrayi32(35),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::StageMelonFlag => SaveFileData {
-            variable_name: "m_iStageMelonFlag".into(),
-            variable_name_simple: "Melons Obtained Bitfield".into(),
-            offset: 0xD4C,
-            int_type: SaveDataIntType::Arrayi32(35),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::StageCherryNum => SaveFileData {
-            variable_name: "m_iStageCherryGetNum".into(),
-            variable_name_simple: "Cherry Per Level".into(),
-            offset: 0xE78,
-            int_type: SaveDataIntType::Arrayi32(35),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::StageStrawberryNum => SaveFileData {
-            variable_name: "m_iStageStrawberryGetNum".into(),
-            variable_name_simple: "Strawberry Per Level".into(),
-            offset: 0xFA4,
-            int_type: SaveDataIntType::Arrayi32(35),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::StageOrangeNum => SaveFileData {
-            variable_name: "m_iStageOrangeGetNum".into(),
-            variable_name_simple: "Orange Per Level".into(),
-            offset: 0x10D0,
-            int_type: SaveDataIntType::Arrayi32(35),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::StageAppleNum => SaveFileData {
-            variable_name: "m_iStageAppleGetNum".into(),
-            variable_name_simple: "Apple Per Level".into(),
-            offset: 0x1FFC,
-            int_type: SaveDataIntType::Arrayi32(35),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::StageMelonNum => SaveFileData {
-            variable_name: "m_iStageMelonGetNum".into(),
-            variable_name_simple: "Melon Per Level".into(),
-            offset: 0x1328,
-            int_type: SaveDataIntType::Arrayi32(35),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::VillageCherryFlag => SaveFileData {
-            variable_name: "m_iVillageCherryFlag".into(),
-            variable_name_simple: "Village Cherry Flags".into(),
-            offset: 0x1454,
-            int_type: SaveDataIntType::Arrayi32(26),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::VillageStrawberryFlag => SaveFileData {
-            variable_name: "m_iVillageStrawberryFlag".into(),
-            variable_name_simple: "Village Strawberry Flags".into(),
-            offset: 0x01610,
-            int_type: SaveDataIntType::Arrayi32(29),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::VillageOrangeFlag => SaveFileData {
-            variable_name: "m_iVillageOrangeFlag".into(),
-            variable_name_simple: "Village Orange Flags".into(),
-            offset: 0x17CC,
-            int_type: SaveDataIntType::Arrayi32(17),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::VillageAppleFlag => SaveFileData {
-            variable_name: "m_iVillageAppleFlag".into(),
-            variable_name_simple: "Village Apple Flags".into(),
-            offset: 0x1988,
-            int_type: SaveDataIntType::Arrayi32(22),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::VillageMelonFlag => SaveFileData {
-            variable_name: "m_iVillageMelonFlag".into(),
-            variable_name_simple: "Village Melon Flags".into(),
-            offset: 0x1B44,
-            int_type: SaveDataIntType::Arrayi32(32),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::VillageCherryGetNum => SaveFileData {
-            variable_name: "m_iVillageCherryGetNum".into(),
-            variable_name_simple: "Village Cherries".into(),
-            offset: 0x1D00,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::VillageStrawberryGetNum => SaveFileData {
-            variable_name: "m_iVillageStrawberryGetNum".into(),
-            variable_name_simple: "Village Strawberry".into(),
-            offset: 0x1D04,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::VillageOrangeGetNum => SaveFileData {
-            variable_name: "m_iVillageOrangeGetNum".into(),
-            variable_name_simple: "Village Oranges".into(),
-            offset: 0x1D08,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::VillageAppleGetNum => SaveFileData {
-            variable_name: "m_iVillageAppleGetNum".into(),
-            variable_name_simple: "Village Apples".into(),
-            offset: 0x1D0C,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::VillageMelonGetNum => SaveFileData {
-            variable_name: "m_iVillageMelonGetNum".into(),
-            variable_name_simple: "Village Melons".into(),
-            offset: 0x1D10,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::VillageGFFlag => SaveFileData {
-            variable_name: "m_iVillageGFFlag".into(),
-            variable_name_simple: "Village GF Bitfield".into(),
-            offset: 0x1D14,
-            int_type: SaveDataIntType::U32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::CameraMode => SaveFileData {
-            variable_name: "m_iCameraMode".into(),
-            variable_name_simple: "Camera Mode".into(),
-            offset: 0x1D18,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::CameraSpeedY => SaveFileData {
-            variable_name: "m_iCameraSpeedUD".into(),
-            variable_name_simple: "Camera Sensitivity Y".into(),
-            offset: 0x1D1C,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::CameraSpeedX => SaveFileData {
-            variable_name: "m_iCameraSpeedLR".into(),
-            variable_name_simple: "Camera Sensitivity X".into(),
-            offset: 0x1D20,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::CameraControlY => SaveFileData {
-            variable_name: "m_iCameraControlUD".into(),
-            variable_name_simple: "Reverse Vertical Camera".into(),
-            offset: 0x1D24,
-            int_type: SaveDataIntType::Bool, // game code says i32
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::CameraControlX => SaveFileData {
-            variable_name: "m_iCameraControlLR".into(),
-            variable_name_simple: "Reverse Horizontal Camera".into(),
-            offset: 0x1D28,
-            int_type: SaveDataIntType::Bool, // game code says i32
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::CameraAssistFlag => SaveFileData {
-            variable_name: "m_iCameraAssistFlag".into(),
-            variable_name_simple: "Disable Camera Assist".into(),
-            offset: 0x1D2C,
-            int_type: SaveDataIntType::Bool, // game code says i32
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::CameraYAutoRotateFlag => SaveFileData {
-            variable_name: "m_iCameraYAutoRotFlag".into(),
-            variable_name_simple: "Disable Player Tracking Camera".into(),
-            offset: 0x1D30,
-            int_type: SaveDataIntType::Bool, // game code says i32
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::SwimControlY => SaveFileData {
-            variable_name: "m_iSwimControlUD".into(),
-            variable_name_simple: "Reverse Vertical Swim Control (?)".into(),
-            offset: 0x1D34,
-            int_type: SaveDataIntType::Arrayi32(2),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::MissionFlag => SaveFileData {
-            variable_name: "m_iMissionFlag".into(),
-            variable_name_simple: "Mission Complete Flags".into(),
-            offset: 0x1D3C,
-            int_type: SaveDataIntType::Arrayi32(107), // number of missions
-            slot_base_add,
-            var: req_data,
-        },
-        // TODO mission progress here
-        SaveDataVar::MissionRewardFlag => SaveFileData {
-            variable_name: "m_iMissionRewardFlag".into(),
-            variable_name_simple: "Mission Reward Flags".into(),
-            offset: 0x21EC,
-            int_type: SaveDataIntType::Arrayi32(LEVELS_COUNT + 1),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::PlayerSkinId => SaveFileData {
-            variable_name: "m_iPlayerSkinId".into(),
-            variable_name_simple: "Player Skin".into(),
-            offset: 0x23E0,
-            // game says it's an array of 2 but it's easier here to make this 2 separate vars
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::PlayerSkinId2 => SaveFileData {
-            variable_name: "m_iPlayerSkinId".into(),
-            variable_name_simple: "Player Skin P2".into(),
-            offset: 0x23E4,
-            // game says it's an array of 2 but it's easier here to make this 2 separate vars
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::FigureInfo => SaveFileData {
-            variable_name: "m_sFigureInfo".into(),
-            variable_name_simple: "Figure Info".into(),
-            offset: 0x23E8,
-            // TODO verify len and how this works
-            int_type: SaveDataIntType::Arrayi32(20),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::FigureDisplayInfo => SaveFileData {
-            variable_name: "m_sFigureDisplayInfo".into(),
-            variable_name_simple: "Figure Display Info".into(),
-            offset: 0x2BE8,
-            int_type: SaveDataIntType::SFigureDisplayInfoArray(50),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::GashaFlag => SaveFileData {
-            variable_name: "m_iGashaFlag".into(),
-            variable_name_simple: "Gasha Flag List".into(),
-            offset: 0x2D78,
-            int_type: SaveDataIntType::Arrayi32(100),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::GashaLotteryNum => SaveFileData {
-            variable_name: "m_iGashaLotteryNum".into(),
-            variable_name_simple: "Gasha Lottery Number".into(),
-            offset: 0x2DA8,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::FlipKillNum => SaveFileData {
-            variable_name: "m_iFlipKillNum".into(),
-            variable_name_simple: "Flip Kill Number".into(),
-            offset: 0x2DAC,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::SupperHipStunNum => SaveFileData {
-            variable_name: "m_iSuperHipStunNum".into(),
-            variable_name_simple: "Super Butt Bounce Stun Number".into(),
-            offset: 0x2DB0,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::SuperDolphinKillNum => SaveFileData {
-            variable_name: "m_iSuperDolphinKillNum".into(),
-            variable_name_simple: "Super Dolphin Kick Kill Number".into(),
-            offset: 0x2DB4,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::UnlockStageSelectFlag => SaveFileData {
-            variable_name: "m_uUnlockStageSelectFlag".into(),
-            variable_name_simple: "Unlock Stage Select Bitfield".into(),
-            offset: 0x2DB8,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::GameLevel => SaveFileData {
-            variable_name: "m_iGameLevel".into(),
-            variable_name_simple: "Game Level".into(),
-            offset: 0x2DBC,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::EnterPast => SaveFileData {
-            variable_name: "m_bEnterPast".into(),
-            variable_name_simple: "Enter Past".into(),
-            offset: 0x2DC0,
-            int_type: SaveDataIntType::Bool,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::OriginalFlag => SaveFileData {
-            variable_name: "m_iOriginalFlag".into(),
-            variable_name_simple: "Pac-Man Flags".into(),
-            offset: 0x2DC0,
-            int_type: SaveDataIntType::Arrayi32(3),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::OriginalStageNum => SaveFileData {
-            variable_name: "m_iOriginalStageNum".into(),
-            variable_name_simple: "Pac-Man Best Stage".into(),
-            offset: 0x2DD0,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::OriginalHighScore => SaveFileData {
-            variable_name: "m_iOriginalHighScore".into(),
-            variable_name_simple: "Pac-Man High Score".into(),
-            offset: 0x2DD4,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::PacManiaStageNum => SaveFileData {
-            variable_name: "m_iPacManiaStageNum".into(),
-            variable_name_simple: "Pac-Mania Best Stage".into(),
-            offset: 0x2DD8,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::PacmaniaHighScore => SaveFileData {
-            variable_name: "m_iPacManiaHighScore".into(),
-            variable_name_simple: "Pac-Mania High Score".into(),
-            offset: 0x2DDC,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::PacAttackLevelNum => SaveFileData {
-            variable_name: "m_iPacAttackLevelNum".into(),
-            variable_name_simple: "Pac-Attack Best Level".into(),
-            offset: 0x2DE0,
-            int_type: SaveDataIntType::Arrayi32(4),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::PacAttackHighScore => SaveFileData {
-            variable_name: "m_iPacAttackHighScore".into(),
-            variable_name_simple: "Pac-Attack High Score".into(),
-            offset: 0x2DF0,
-            int_type: SaveDataIntType::Arrayi32(4),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::HelpFlag => SaveFileData {
-            variable_name: "m_iHelpFlag".into(),
-            variable_name_simple: "Help Flags".into(),
-            offset: 0x2E00,
-            int_type: SaveDataIntType::Arrayi32(50),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::DroneSkinFlag => SaveFileData {
-            variable_name: "m_iPlayerDroneSkinId".into(),
-            variable_name_simple: "Drone Skin ID".into(),
-            offset: 0x2EF0,
-            int_type: SaveDataIntType::Arrayi32(10),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::DroneSkinID => SaveFileData {
-            variable_name: "m_iPlayerDroneSkinFlag".into(),
-            variable_name_simple: "Drone Skin Flag".into(),
-            offset: 0x2EC8,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::DroneReticleSpeed => SaveFileData {
-            variable_name: "m_iDroneReticleSpeed".into(),
-            variable_name_simple: "Drone Reticle Speed".into(),
-            offset: 0x2EF4,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::DroneReticleColor => SaveFileData {
-            variable_name: "m_iDroneReticleColor".into(),
-            variable_name_simple: "Drone Reticle Color".into(),
-            offset: 0x2EF8,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::DroneVacuumRangeFlag => SaveFileData {
-            variable_name: "m_iDroneVacuumRangeFlag".into(),
-            variable_name_simple: "Drone Vacuum Range Flag".into(),
-            offset: 0x2EFC,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::EarlyBonusFigureFlag => SaveFileData {
-            variable_name: "m_iEarlyBonusFigureFlag".into(),
-            variable_name_simple: "Early Access Bonus Figure".into(),
-            offset: 0x2F00,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::KeyConfigP1 => SaveFileData {
-            variable_name: "m_keyconfigSave1P".into(),
-            variable_name_simple: "Inputs Config".into(),
-            offset: 0x2F04,
-            int_type: SaveDataIntType::Arrayi32(672),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::KeyConfigP2 => SaveFileData {
-            variable_name: "m_keyconfigSave2P".into(),
-            variable_name_simple: "Inputs Config Player 2".into(),
-            offset: 0x3984,
-            int_type: SaveDataIntType::Arrayi32(672),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::GashaDLCFlag => SaveFileData {
-            variable_name: "m_iGashaDLCFlag".into(),
-            variable_name_simple: "Gasha DLC Flag".into(),
-            offset: 0x4404,
-            int_type: SaveDataIntType::Arrayi32(4),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::FigureInfoDLC => SaveFileData {
-            variable_name: "m_sFigureInfoDLC".into(),
-            variable_name_simple: "Figure Info DLC".into(),
-            offset: 0x4414,
-            int_type: SaveDataIntType::Arrayi32(16),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::EnterSonic => SaveFileData {
-            variable_name: "m_bEnterSonic".into(),
-            variable_name_simple: "Enter Sonic".into(),
-            offset: 0x4454,
-            int_type: SaveDataIntType::Bool,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::VillageSonicFlag => SaveFileData {
-            variable_name: "m_uVillageSonicFlag".into(),
-            variable_name_simple: "Village Sonic Flag".into(),
-            offset: 0x4458,
-            int_type: SaveDataIntType::U32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::LoadInitSceneCollab => SaveFileData {
-            variable_name: "m_iLoadInitSceneCollabo".into(),
-            variable_name_simple: "Load Init Scene (Sonic)".into(),
-            offset: 0x445C,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::LoadInitSceneKind => SaveFileData {
-            variable_name: "m_iLoadInitSceneKind".into(),
-            variable_name_simple: "Load Init Scene Kind".into(),
-            offset: 0x4560,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::LastStageIdCollab => SaveFileData {
-            variable_name: "m_iLastStageIdCollabo".into(),
-            variable_name_simple: "Last Stage ID (Sonic)".into(),
-            offset: 0x4564,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::LastStageIdKindCollab => SaveFileData {
-            variable_name: "m_iLastStageIdKind".into(),
-            variable_name_simple: "Last Stage ID Kind (Sonic)".into(),
-            offset: 0x4568,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::PlayerSkinIdCollab => SaveFileData {
-            variable_name: "m_iPlayerSkinIdCollabo".into(),
-            variable_name_simple: "Player Skin (Sonic)".into(),
-            offset: 0x456C,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::FigureDisplayInfoCollab => SaveFileData {
-            variable_name: "m_iFigureDisplayInfoCollabo".into(),
-            variable_name_simple: "Figure Display Info (Sonic)".into(),
-            offset: 0x4570,
-            int_type: SaveDataIntType::Arrayi32(114),
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::JukeBoxBGMCollab => SaveFileData {
-            variable_name: "m_iJukeBoxBGMKindCollabo".into(),
-            variable_name_simple: "Jukebox Music (Sonic)".into(),
-            offset: 0x4638,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-        SaveDataVar::JukeBoxModeCollab => SaveFileData {
-            variable_name: "m_iJukeBoxModeCollabo".into(),
-            variable_name_simple: "Jukebox Mode (Sonic)".into(),
-            offset: 0x463C,
-            int_type: SaveDataIntType::I32,
-            slot_base_add,
-            var: req_data,
-        },
-    }
+pub fn get_save_file_variable(req_data: SaveDataVar, slot: u8) -> Result<SaveFileData, SaveError> {
+    req_data.field().at_slot(slot)
 }
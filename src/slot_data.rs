@@ -0,0 +1,667 @@
+use crate::{
+    error::SaveError,
+    save_data_info::{LEVELS_COUNT, MAZES_COUNT, SAVE_SLOT_SIZE, SaveDataIntType, SaveDataVar},
+    save_file_parser::{SFigureDisplayInfo, get_save_data_bytes},
+};
+
+/// All the known variables of a save slot as Rust values, in the order of `SAVE_FIELDS`.
+///
+/// `decode` keeps a copy of the slot bytes and `encode` writes the variables on top of it, so the
+/// bytes no variable describes come back exactly as they were read. Only the bytes that changed
+/// are written, some variables of the table overlap and an edit of one of them isn't undone by
+/// the other.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotData {
+    pub file_exists: bool,
+    pub save_date: [u8; 16],
+    pub play_time_hours: i32,
+    pub play_time_minutes: i32,
+    pub play_time_seconds: i32,
+    pub stage_flag_list: [u8; LEVELS_COUNT as usize],
+    pub score_list: [i32; LEVELS_COUNT as usize],
+    pub time_trial_list: [i32; LEVELS_COUNT as usize],
+    pub time_trial_coop_list: [i32; LEVELS_COUNT as usize],
+    pub friends_flag_list: [u8; 9],
+    pub friends_talk_list: [i32; 34],
+    pub stage_maze_flag_list: [u8; MAZES_COUNT as usize],
+    pub last_stage_id: i32,
+    pub last_game_mode_id: i32,
+    pub lives: i32,
+    pub maze_flag_list: [u8; MAZES_COUNT as usize],
+    pub mazes_score_list: [i32; MAZES_COUNT as usize],
+    pub fruits_get_num: i32,
+    pub capsule_get_num: i32,
+    pub medal_get_num: i32,
+    pub bomb_dot_kill_num: i32,
+    pub dot_kill_num: i32,
+    pub rev_roll_kill_num: i32,
+    pub butt_bounce_kill_num: i32,
+    pub ghost_kill_num: i32,
+    pub magic_key_unlocked: bool,
+    pub true_ending: bool,
+    pub marathon_unlocked: bool,
+    pub marathon_high_score: i32,
+    pub marathon_clear: i32,
+    pub last_gi_save_area: i32,
+    pub load_init_scene: i32,
+    pub dlc_apply_flag: i32,
+    pub juke_box_bgm: i32,
+    pub juke_box_mode: i32,
+    pub juke_box_loop: i32,
+    pub juke_box_order: i32,
+    pub juke_box_flag: i32,
+    pub juke_box_unlock_flag_list: [u8; 83],
+    pub medal_num: i32,
+    pub capsule_flag: [i32; 150],
+    pub stage_cherry_flag: [i32; 35],
+    pub stage_strawberry_flag: [i32; 35],
+    pub stage_orange_flag: [i32; 35],
+    pub stage_apple_flag: [i32; 35],
+    pub stage_melon_flag: [i32; 35],
+    pub stage_cherry_num: [i32; 35],
+    pub stage_strawberry_num: [i32; 35],
+    pub stage_orange_num: [i32; 35],
+    pub stage_apple_num: [i32; 35],
+    pub stage_melon_num: [i32; 35],
+    pub village_cherry_flag: [i32; 26],
+    pub village_strawberry_flag: [i32; 29],
+    pub village_orange_flag: [i32; 17],
+    pub village_apple_flag: [i32; 22],
+    pub village_melon_flag: [i32; 32],
+    pub village_cherry_get_num: i32,
+    pub village_strawberry_get_num: i32,
+    pub village_orange_get_num: i32,
+    pub village_apple_get_num: i32,
+    pub village_melon_get_num: i32,
+    pub village_gf_flag: u32,
+    pub camera_mode: i32,
+    pub camera_speed_y: i32,
+    pub camera_speed_x: i32,
+    pub camera_control_y: bool,
+    pub camera_control_x: bool,
+    pub camera_assist_flag: bool,
+    pub camera_y_auto_rotate_flag: bool,
+    pub swim_control_y: [i32; 2],
+    pub mission_flag: [i32; 107],
+    pub mission_reward_flag: [i32; LEVELS_COUNT as usize + 1],
+    pub player_skin_id: i32,
+    pub player_skin_id2: i32,
+    pub figure_info: [i32; 20],
+    pub figure_display_info: [SFigureDisplayInfo; 50],
+    pub gasha_flag: [i32; 100],
+    pub gasha_lottery_num: i32,
+    pub flip_kill_num: i32,
+    pub supper_hip_stun_num: i32,
+    pub super_dolphin_kill_num: i32,
+    pub unlock_stage_select_flag: u32,
+    pub game_level: i32,
+    pub enter_past: bool,
+    pub original_flag: [i32; 3],
+    pub original_stage_num: i32,
+    pub original_high_score: i32,
+    pub pac_mania_stage_num: i32,
+    pub pacmania_high_score: i32,
+    pub pac_attack_level_num: [i32; 4],
+    pub pac_attack_high_score: [i32; 4],
+    pub help_flag: [i32; 50],
+    pub drone_skin_flag: [i32; 10],
+    pub drone_skin_id: i32,
+    pub drone_reticle_speed: i32,
+    pub drone_reticle_color: i32,
+    pub drone_vacuum_range_flag: i32,
+    pub early_bonus_figure_flag: i32,
+    pub key_config_p1: [i32; 672],
+    pub key_config_p2: [i32; 672],
+    pub gasha_dlc_flag: [i32; 4],
+    pub figure_info_dlc: [i32; 16],
+    pub enter_sonic: bool,
+    pub village_sonic_flag: u32,
+    pub load_init_scene_collab: i32,
+    pub load_init_scene_kind: i32,
+    pub last_stage_id_collab: i32,
+    pub last_stage_id_kind_collab: i32,
+    pub player_skin_id_collab: i32,
+    pub figure_display_info_collab: [SFigureDisplayInfo; 57],
+    pub juke_box_bgm_collab: i32,
+    pub juke_box_mode_collab: i32,
+    raw: Vec<u8>,
+}
+
+impl SlotData {
+    /// Decodes the bytes of one save slot, `bytes` has to be exactly one slot long.
+    pub fn decode(bytes: &[u8]) -> Result<SlotData, SaveError> {
+        if bytes.len() != SAVE_SLOT_SIZE as usize {
+            return Err(SaveError::SizeMismatch {
+                expected: SAVE_SLOT_SIZE as usize,
+                found: bytes.len(),
+            });
+        }
+        let reader = SlotReader { bytes };
+        Ok(SlotData {
+            file_exists: reader.read_bool(SaveDataVar::FileExists)?,
+            save_date: reader.read_u8_array(SaveDataVar::SaveDate)?,
+            play_time_hours: reader.read_i32(SaveDataVar::PlayTimeHours)?,
+            play_time_minutes: reader.read_i32(SaveDataVar::PlayTimeMinutes)?,
+            play_time_seconds: reader.read_i32(SaveDataVar::PlayTimeSeconds)?,
+            stage_flag_list: reader.read_u8_array(SaveDataVar::StageFlagList)?,
+            score_list: reader.read_i32_array(SaveDataVar::ScoreList)?,
+            time_trial_list: reader.read_i32_array(SaveDataVar::TimeTrialList)?,
+            time_trial_coop_list: reader.read_i32_array(SaveDataVar::TimeTrialCoopList)?,
+            friends_flag_list: reader.read_u8_array(SaveDataVar::FriendsFlagList)?,
+            friends_talk_list: reader.read_i32_array(SaveDataVar::FriendsTalkList)?,
+            stage_maze_flag_list: reader.read_u8_array(SaveDataVar::StageMazeFlagList)?,
+            last_stage_id: reader.read_i32(SaveDataVar::LastStageId)?,
+            last_game_mode_id: reader.read_i32(SaveDataVar::LastGameModeId)?,
+            lives: reader.read_i32(SaveDataVar::Lives)?,
+            maze_flag_list: reader.read_u8_array(SaveDataVar::MazeFlagList)?,
+            mazes_score_list: reader.read_i32_array(SaveDataVar::MazesScoreList)?,
+            fruits_get_num: reader.read_i32(SaveDataVar::FruitsGetNum)?,
+            capsule_get_num: reader.read_i32(SaveDataVar::CapsuleGetNum)?,
+            medal_get_num: reader.read_i32(SaveDataVar::MedalGetNum)?,
+            bomb_dot_kill_num: reader.read_i32(SaveDataVar::BombDotKillNum)?,
+            dot_kill_num: reader.read_i32(SaveDataVar::DotKillNum)?,
+            rev_roll_kill_num: reader.read_i32(SaveDataVar::RevRollKillNum)?,
+            butt_bounce_kill_num: reader.read_i32(SaveDataVar::ButtBounceKillNum)?,
+            ghost_kill_num: reader.read_i32(SaveDataVar::GhostKillNum)?,
+            magic_key_unlocked: reader.read_bool(SaveDataVar::MagicKeyUnlocked)?,
+            true_ending: reader.read_bool(SaveDataVar::TrueEnding)?,
+            marathon_unlocked: reader.read_bool(SaveDataVar::MarathonUnlocked)?,
+            marathon_high_score: reader.read_i32(SaveDataVar::MarathonHighScore)?,
+            marathon_clear: reader.read_i32(SaveDataVar::MarathonClear)?,
+            last_gi_save_area: reader.read_i32(SaveDataVar::LastGISaveArea)?,
+            load_init_scene: reader.read_i32(SaveDataVar::LoadInitScene)?,
+            dlc_apply_flag: reader.read_i32(SaveDataVar::DLCApplyFlag)?,
+            juke_box_bgm: reader.read_i32(SaveDataVar::JukeBoxBGM)?,
+            juke_box_mode: reader.read_i32(SaveDataVar::JukeBoxMode)?,
+            juke_box_loop: reader.read_i32(SaveDataVar::JukeBoxLoop)?,
+            juke_box_order: reader.read_i32(SaveDataVar::JukeBoxOrder)?,
+            juke_box_flag: reader.read_i32(SaveDataVar::JukeBoxFlag)?,
+            juke_box_unlock_flag_list: reader.read_u8_array(SaveDataVar::JukeBoxUnlockFlagList)?,
+            medal_num: reader.read_i32(SaveDataVar::MedalNum)?,
+            capsule_flag: reader.read_i32_array(SaveDataVar::CapsuleFlag)?,
+            stage_cherry_flag: reader.read_i32_array(SaveDataVar::StageCherryFlag)?,
+            stage_strawberry_flag: reader.read_i32_array(SaveDataVar::StageStrawberryFlag)?,
+            stage_orange_flag: reader.read_i32_array(SaveDataVar::StageOrangeFlag)?,
+            stage_apple_flag: reader.read_i32_array(SaveDataVar::StageAppleFlag)?,
+            stage_melon_flag: reader.read_i32_array(SaveDataVar::StageMelonFlag)?,
+            stage_cherry_num: reader.read_i32_array(SaveDataVar::StageCherryNum)?,
+            stage_strawberry_num: reader.read_i32_array(SaveDataVar::StageStrawberryNum)?,
+            stage_orange_num: reader.read_i32_array(SaveDataVar::StageOrangeNum)?,
+            stage_apple_num: reader.read_i32_array(SaveDataVar::StageAppleNum)?,
+            stage_melon_num: reader.read_i32_array(SaveDataVar::StageMelonNum)?,
+            village_cherry_flag: reader.read_i32_array(SaveDataVar::VillageCherryFlag)?,
+            village_strawberry_flag: reader.read_i32_array(SaveDataVar::VillageStrawberryFlag)?,
+            village_orange_flag: reader.read_i32_array(SaveDataVar::VillageOrangeFlag)?,
+            village_apple_flag: reader.read_i32_array(SaveDataVar::VillageAppleFlag)?,
+            village_melon_flag: reader.read_i32_array(SaveDataVar::VillageMelonFlag)?,
+            village_cherry_get_num: reader.read_i32(SaveDataVar::VillageCherryGetNum)?,
+            village_strawberry_get_num: reader.read_i32(SaveDataVar::VillageStrawberryGetNum)?,
+            village_orange_get_num: reader.read_i32(SaveDataVar::VillageOrangeGetNum)?,
+            village_apple_get_num: reader.read_i32(SaveDataVar::VillageAppleGetNum)?,
+            village_melon_get_num: reader.read_i32(SaveDataVar::VillageMelonGetNum)?,
+            village_gf_flag: reader.read_u32(SaveDataVar::VillageGFFlag)?,
+            camera_mode: reader.read_i32(SaveDataVar::CameraMode)?,
+            camera_speed_y: reader.read_i32(SaveDataVar::CameraSpeedY)?,
+            camera_speed_x: reader.read_i32(SaveDataVar::CameraSpeedX)?,
+            camera_control_y: reader.read_bool(SaveDataVar::CameraControlY)?,
+            camera_control_x: reader.read_bool(SaveDataVar::CameraControlX)?,
+            camera_assist_flag: reader.read_bool(SaveDataVar::CameraAssistFlag)?,
+            camera_y_auto_rotate_flag: reader.read_bool(SaveDataVar::CameraYAutoRotateFlag)?,
+            swim_control_y: reader.read_i32_array(SaveDataVar::SwimControlY)?,
+            mission_flag: reader.read_i32_array(SaveDataVar::MissionFlag)?,
+            mission_reward_flag: reader.read_i32_array(SaveDataVar::MissionRewardFlag)?,
+            player_skin_id: reader.read_i32(SaveDataVar::PlayerSkinId)?,
+            player_skin_id2: reader.read_i32(SaveDataVar::PlayerSkinId2)?,
+            figure_info: reader.read_i32_array(SaveDataVar::FigureInfo)?,
+            figure_display_info: reader.read_figure_info_array(SaveDataVar::FigureDisplayInfo)?,
+            gasha_flag: reader.read_i32_array(SaveDataVar::GashaFlag)?,
+            gasha_lottery_num: reader.read_i32(SaveDataVar::GashaLotteryNum)?,
+            flip_kill_num: reader.read_i32(SaveDataVar::FlipKillNum)?,
+            supper_hip_stun_num: reader.read_i32(SaveDataVar::SupperHipStunNum)?,
+            super_dolphin_kill_num: reader.read_i32(SaveDataVar::SuperDolphinKillNum)?,
+            unlock_stage_select_flag: reader.read_u32(SaveDataVar::UnlockStageSelectFlag)?,
+            game_level: reader.read_i32(SaveDataVar::GameLevel)?,
+            enter_past: reader.read_bool(SaveDataVar::EnterPast)?,
+            original_flag: reader.read_i32_array(SaveDataVar::OriginalFlag)?,
+            original_stage_num: reader.read_i32(SaveDataVar::OriginalStageNum)?,
+            original_high_score: reader.read_i32(SaveDataVar::OriginalHighScore)?,
+            pac_mania_stage_num: reader.read_i32(SaveDataVar::PacManiaStageNum)?,
+            pacmania_high_score: reader.read_i32(SaveDataVar::PacmaniaHighScore)?,
+            pac_attack_level_num: reader.read_i32_array(SaveDataVar::PacAttackLevelNum)?,
+            pac_attack_high_score: reader.read_i32_array(SaveDataVar::PacAttackHighScore)?,
+            help_flag: reader.read_i32_array(SaveDataVar::HelpFlag)?,
+            drone_skin_flag: reader.read_i32_array(SaveDataVar::DroneSkinFlag)?,
+            drone_skin_id: reader.read_i32(SaveDataVar::DroneSkinID)?,
+            drone_reticle_speed: reader.read_i32(SaveDataVar::DroneReticleSpeed)?,
+            drone_reticle_color: reader.read_i32(SaveDataVar::DroneReticleColor)?,
+            drone_vacuum_range_flag: reader.read_i32(SaveDataVar::DroneVacuumRangeFlag)?,
+            early_bonus_figure_flag: reader.read_i32(SaveDataVar::EarlyBonusFigureFlag)?,
+            key_config_p1: reader.read_i32_array(SaveDataVar::KeyConfigP1)?,
+            key_config_p2: reader.read_i32_array(SaveDataVar::KeyConfigP2)?,
+            gasha_dlc_flag: reader.read_i32_array(SaveDataVar::GashaDLCFlag)?,
+            figure_info_dlc: reader.read_i32_array(SaveDataVar::FigureInfoDLC)?,
+            enter_sonic: reader.read_bool(SaveDataVar::EnterSonic)?,
+            village_sonic_flag: reader.read_u32(SaveDataVar::VillageSonicFlag)?,
+            load_init_scene_collab: reader.read_i32(SaveDataVar::LoadInitSceneCollab)?,
+            load_init_scene_kind: reader.read_i32(SaveDataVar::LoadInitSceneKind)?,
+            last_stage_id_collab: reader.read_i32(SaveDataVar::LastStageIdCollab)?,
+            last_stage_id_kind_collab: reader.read_i32(SaveDataVar::LastStageIdKindCollab)?,
+            player_skin_id_collab: reader.read_i32(SaveDataVar::PlayerSkinIdCollab)?,
+            figure_display_info_collab: reader
+                .read_figure_info_array(SaveDataVar::FigureDisplayInfoCollab)?,
+            juke_box_bgm_collab: reader.read_i32(SaveDataVar::JukeBoxBGMCollab)?,
+            juke_box_mode_collab: reader.read_i32(SaveDataVar::JukeBoxModeCollab)?,
+            raw: bytes.to_vec(),
+        })
+    }
+
+    /// Gives back the bytes of the slot with the current value of every variable.
+    /// Fails if two changed variables changed the same bytes.
+    pub fn encode(&self) -> Result<Vec<u8>, SaveError> {
+        self.write_all().finish()
+    }
+
+    fn write_all(&self) -> SlotWriter<'_> {
+        let mut writer = SlotWriter::new(&self.raw);
+        writer.write_bool(SaveDataVar::FileExists, self.file_exists);
+        writer.write_u8_array(SaveDataVar::SaveDate, &self.save_date);
+        writer.write_i32(SaveDataVar::PlayTimeHours, self.play_time_hours);
+        writer.write_i32(SaveDataVar::PlayTimeMinutes, self.play_time_minutes);
+        writer.write_i32(SaveDataVar::PlayTimeSeconds, self.play_time_seconds);
+        writer.write_u8_array(SaveDataVar::StageFlagList, &self.stage_flag_list);
+        writer.write_i32_array(SaveDataVar::ScoreList, &self.score_list);
+        writer.write_i32_array(SaveDataVar::TimeTrialList, &self.time_trial_list);
+        writer.write_i32_array(SaveDataVar::TimeTrialCoopList, &self.time_trial_coop_list);
+        writer.write_u8_array(SaveDataVar::FriendsFlagList, &self.friends_flag_list);
+        writer.write_i32_array(SaveDataVar::FriendsTalkList, &self.friends_talk_list);
+        writer.write_u8_array(SaveDataVar::StageMazeFlagList, &self.stage_maze_flag_list);
+        writer.write_i32(SaveDataVar::LastStageId, self.last_stage_id);
+        writer.write_i32(SaveDataVar::LastGameModeId, self.last_game_mode_id);
+        writer.write_i32(SaveDataVar::Lives, self.lives);
+        writer.write_u8_array(SaveDataVar::MazeFlagList, &self.maze_flag_list);
+        writer.write_i32_array(SaveDataVar::MazesScoreList, &self.mazes_score_list);
+        writer.write_i32(SaveDataVar::FruitsGetNum, self.fruits_get_num);
+        writer.write_i32(SaveDataVar::CapsuleGetNum, self.capsule_get_num);
+        writer.write_i32(SaveDataVar::MedalGetNum, self.medal_get_num);
+        writer.write_i32(SaveDataVar::BombDotKillNum, self.bomb_dot_kill_num);
+        writer.write_i32(SaveDataVar::DotKillNum, self.dot_kill_num);
+        writer.write_i32(SaveDataVar::RevRollKillNum, self.rev_roll_kill_num);
+        writer.write_i32(SaveDataVar::ButtBounceKillNum, self.butt_bounce_kill_num);
+        writer.write_i32(SaveDataVar::GhostKillNum, self.ghost_kill_num);
+        writer.write_bool(SaveDataVar::MagicKeyUnlocked, self.magic_key_unlocked);
+        writer.write_bool(SaveDataVar::TrueEnding, self.true_ending);
+        writer.write_bool(SaveDataVar::MarathonUnlocked, self.marathon_unlocked);
+        writer.write_i32(SaveDataVar::MarathonHighScore, self.marathon_high_score);
+        writer.write_i32(SaveDataVar::MarathonClear, self.marathon_clear);
+        writer.write_i32(SaveDataVar::LastGISaveArea, self.last_gi_save_area);
+        writer.write_i32(SaveDataVar::LoadInitScene, self.load_init_scene);
+        writer.write_i32(SaveDataVar::DLCApplyFlag, self.dlc_apply_flag);
+        writer.write_i32(SaveDataVar::JukeBoxBGM, self.juke_box_bgm);
+        writer.write_i32(SaveDataVar::JukeBoxMode, self.juke_box_mode);
+        writer.write_i32(SaveDataVar::JukeBoxLoop, self.juke_box_loop);
+        writer.write_i32(SaveDataVar::JukeBoxOrder, self.juke_box_order);
+        writer.write_i32(SaveDataVar::JukeBoxFlag, self.juke_box_flag);
+        writer.write_u8_array(
+            SaveDataVar::JukeBoxUnlockFlagList,
+            &self.juke_box_unlock_flag_list,
+        );
+        writer.write_i32(SaveDataVar::MedalNum, self.medal_num);
+        writer.write_i32_array(SaveDataVar::CapsuleFlag, &self.capsule_flag);
+        writer.write_i32_array(SaveDataVar::StageCherryFlag, &self.stage_cherry_flag);
+        writer.write_i32_array(
+            SaveDataVar::StageStrawberryFlag,
+            &self.stage_strawberry_flag,
+        );
+        writer.write_i32_array(SaveDataVar::StageOrangeFlag, &self.stage_orange_flag);
+        writer.write_i32_array(SaveDataVar::StageAppleFlag, &self.stage_apple_flag);
+        writer.write_i32_array(SaveDataVar::StageMelonFlag, &self.stage_melon_flag);
+        writer.write_i32_array(SaveDataVar::StageCherryNum, &self.stage_cherry_num);
+        writer.write_i32_array(SaveDataVar::StageStrawberryNum, &self.stage_strawberry_num);
+        writer.write_i32_array(SaveDataVar::StageOrangeNum, &self.stage_orange_num);
+        writer.write_i32_array(SaveDataVar::StageAppleNum, &self.stage_apple_num);
+        writer.write_i32_array(SaveDataVar::StageMelonNum, &self.stage_melon_num);
+        writer.write_i32_array(SaveDataVar::VillageCherryFlag, &self.village_cherry_flag);
+        writer.write_i32_array(
+            SaveDataVar::VillageStrawberryFlag,
+            &self.village_strawberry_flag,
+        );
+        writer.write_i32_array(SaveDataVar::VillageOrangeFlag, &self.village_orange_flag);
+        writer.write_i32_array(SaveDataVar::VillageAppleFlag, &self.village_apple_flag);
+        writer.write_i32_array(SaveDataVar::VillageMelonFlag, &self.village_melon_flag);
+        writer.write_i32(
+            SaveDataVar::VillageCherryGetNum,
+            self.village_cherry_get_num,
+        );
+        writer.write_i32(
+            SaveDataVar::VillageStrawberryGetNum,
+            self.village_strawberry_get_num,
+        );
+        writer.write_i32(
+            SaveDataVar::VillageOrangeGetNum,
+            self.village_orange_get_num,
+        );
+        writer.write_i32(SaveDataVar::VillageAppleGetNum, self.village_apple_get_num);
+        writer.write_i32(SaveDataVar::VillageMelonGetNum, self.village_melon_get_num);
+        writer.write_u32(SaveDataVar::VillageGFFlag, self.village_gf_flag);
+        writer.write_i32(SaveDataVar::CameraMode, self.camera_mode);
+        writer.write_i32(SaveDataVar::CameraSpeedY, self.camera_speed_y);
+        writer.write_i32(SaveDataVar::CameraSpeedX, self.camera_speed_x);
+        writer.write_bool(SaveDataVar::CameraControlY, self.camera_control_y);
+        writer.write_bool(SaveDataVar::CameraControlX, self.camera_control_x);
+        writer.write_bool(SaveDataVar::CameraAssistFlag, self.camera_assist_flag);
+        writer.write_bool(
+            SaveDataVar::CameraYAutoRotateFlag,
+            self.camera_y_auto_rotate_flag,
+        );
+        writer.write_i32_array(SaveDataVar::SwimControlY, &self.swim_control_y);
+        writer.write_i32_array(SaveDataVar::MissionFlag, &self.mission_flag);
+        writer.write_i32_array(SaveDataVar::MissionRewardFlag, &self.mission_reward_flag);
+        writer.write_i32(SaveDataVar::PlayerSkinId, self.player_skin_id);
+        writer.write_i32(SaveDataVar::PlayerSkinId2, self.player_skin_id2);
+        writer.write_i32_array(SaveDataVar::FigureInfo, &self.figure_info);
+        writer.write_figure_info_array(SaveDataVar::FigureDisplayInfo, &self.figure_display_info);
+        writer.write_i32_array(SaveDataVar::GashaFlag, &self.gasha_flag);
+        writer.write_i32(SaveDataVar::GashaLotteryNum, self.gasha_lottery_num);
+        writer.write_i32(SaveDataVar::FlipKillNum, self.flip_kill_num);
+        writer.write_i32(SaveDataVar::SupperHipStunNum, self.supper_hip_stun_num);
+        writer.write_i32(
+            SaveDataVar::SuperDolphinKillNum,
+            self.super_dolphin_kill_num,
+        );
+        writer.write_u32(
+            SaveDataVar::UnlockStageSelectFlag,
+            self.unlock_stage_select_flag,
+        );
+        writer.write_i32(SaveDataVar::GameLevel, self.game_level);
+        writer.write_bool(SaveDataVar::EnterPast, self.enter_past);
+        writer.write_i32_array(SaveDataVar::OriginalFlag, &self.original_flag);
+        writer.write_i32(SaveDataVar::OriginalStageNum, self.original_stage_num);
+        writer.write_i32(SaveDataVar::OriginalHighScore, self.original_high_score);
+        writer.write_i32(SaveDataVar::PacManiaStageNum, self.pac_mania_stage_num);
+        writer.write_i32(SaveDataVar::PacmaniaHighScore, self.pacmania_high_score);
+        writer.write_i32_array(SaveDataVar::PacAttackLevelNum, &self.pac_attack_level_num);
+        writer.write_i32_array(SaveDataVar::PacAttackHighScore, &self.pac_attack_high_score);
+        writer.write_i32_array(SaveDataVar::HelpFlag, &self.help_flag);
+        writer.write_i32_array(SaveDataVar::DroneSkinFlag, &self.drone_skin_flag);
+        writer.write_i32(SaveDataVar::DroneSkinID, self.drone_skin_id);
+        writer.write_i32(SaveDataVar::DroneReticleSpeed, self.drone_reticle_speed);
+        writer.write_i32(SaveDataVar::DroneReticleColor, self.drone_reticle_color);
+        writer.write_i32(
+            SaveDataVar::DroneVacuumRangeFlag,
+            self.drone_vacuum_range_flag,
+        );
+        writer.write_i32(
+            SaveDataVar::EarlyBonusFigureFlag,
+            self.early_bonus_figure_flag,
+        );
+        writer.write_i32_array(SaveDataVar::KeyConfigP1, &self.key_config_p1);
+        writer.write_i32_array(SaveDataVar::KeyConfigP2, &self.key_config_p2);
+        writer.write_i32_array(SaveDataVar::GashaDLCFlag, &self.gasha_dlc_flag);
+        writer.write_i32_array(SaveDataVar::FigureInfoDLC, &self.figure_info_dlc);
+        writer.write_bool(SaveDataVar::EnterSonic, self.enter_sonic);
+        writer.write_u32(SaveDataVar::VillageSonicFlag, self.village_sonic_flag);
+        writer.write_i32(
+            SaveDataVar::LoadInitSceneCollab,
+            self.load_init_scene_collab,
+        );
+        writer.write_i32(SaveDataVar::LoadInitSceneKind, self.load_init_scene_kind);
+        writer.write_i32(SaveDataVar::LastStageIdCollab, self.last_stage_id_collab);
+        writer.write_i32(
+            SaveDataVar::LastStageIdKindCollab,
+            self.last_stage_id_kind_collab,
+        );
+        writer.write_i32(SaveDataVar::PlayerSkinIdCollab, self.player_skin_id_collab);
+        writer.write_figure_info_array(
+            SaveDataVar::FigureDisplayInfoCollab,
+            &self.figure_display_info_collab,
+        );
+        writer.write_i32(SaveDataVar::JukeBoxBGMCollab, self.juke_box_bgm_collab);
+        writer.write_i32(SaveDataVar::JukeBoxModeCollab, self.juke_box_mode_collab);
+        writer
+    }
+}
+
+struct SlotReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> SlotReader<'a> {
+    /// The bytes of the variable, checking that the table agrees with the Rust type and its size.
+    fn field_bytes(
+        &self,
+        var: SaveDataVar,
+        is_same_type: fn(&SaveDataIntType) -> bool,
+        size: usize,
+    ) -> Result<&'a [u8], SaveError> {
+        let field = var.field();
+        if !is_same_type(&field.int_type) || field.int_type.size() as usize != size {
+            return Err(SaveError::UnsupportedType {
+                address: field.offset as usize,
+                int_type: field.int_type,
+            });
+        }
+        get_save_data_bytes(self.bytes, field.offset as usize, size)
+    }
+
+    fn read_bool(&self, var: SaveDataVar) -> Result<bool, SaveError> {
+        let is_bool = |int_type: &SaveDataIntType| matches!(int_type, SaveDataIntType::Bool);
+        Ok(self.field_bytes(var, is_bool, 1)?[0] != 0)
+    }
+
+    fn read_u32(&self, var: SaveDataVar) -> Result<u32, SaveError> {
+        let is_u32 = |int_type: &SaveDataIntType| {
+            matches!(int_type, SaveDataIntType::U32 | SaveDataIntType::Bitfield)
+        };
+        let bytes = self.field_bytes(var, is_u32, 4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn read_i32(&self, var: SaveDataVar) -> Result<i32, SaveError> {
+        let is_i32 = |int_type: &SaveDataIntType| matches!(int_type, SaveDataIntType::I32);
+        let bytes = self.field_bytes(var, is_i32, 4)?;
+        Ok(i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn read_i32_array<const N: usize>(&self, var: SaveDataVar) -> Result<[i32; N], SaveError> {
+        let is_i32_array = |int_type: &SaveDataIntType| {
+            matches!(
+                int_type,
+                SaveDataIntType::Arrayi32(_) | SaveDataIntType::BitfieldArray(_)
+            )
+        };
+        let bytes = self.field_bytes(var, is_i32_array, N * 4)?;
+        Ok(std::array::from_fn(|i| {
+            let b = &bytes[i * 4..i * 4 + 4];
+            i32::from_le_bytes([b[0], b[1], b[2], b[3]])
+        }))
+    }
+
+    fn read_u8_array<const N: usize>(&self, var: SaveDataVar) -> Result<[u8; N], SaveError> {
+        let is_u8_array = |int_type: &SaveDataIntType| {
+            matches!(
+                int_type,
+                SaveDataIntType::Arrayu8(_) | SaveDataIntType::ArrayText(_)
+            )
+        };
+        let bytes = self.field_bytes(var, is_u8_array, N)?;
+        Ok(std::array::from_fn(|i| bytes[i]))
+    }
+
+    fn read_figure_info_array<const N: usize>(
+        &self,
+        var: SaveDataVar,
+    ) -> Result<[SFigureDisplayInfo; N], SaveError> {
+        let is_figure_info_array = |int_type: &SaveDataIntType| {
+            matches!(int_type, SaveDataIntType::SFigureDisplayInfoArray(_))
+        };
+        let bytes = self.field_bytes(var, is_figure_info_array, N * 8)?;
+        Ok(std::array::from_fn(|i| {
+            let b = &bytes[i * 8..i * 8 + 8];
+            SFigureDisplayInfo {
+                figure_id: i32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+                angle: f32::from_le_bytes([b[4], b[5], b[6], b[7]]),
+            }
+        }))
+    }
+}
+
+/// Only used on the bytes of a decoded slot, where every variable is known to fit.
+struct SlotWriter<'a> {
+    /// The slot as it was decoded.
+    raw: &'a [u8],
+    bytes: Vec<u8>,
+    /// The variable that changed each byte of the slot.
+    changed_by: Vec<Option<SaveDataVar>>,
+    /// Every variable written and its size, whether it changed or not.
+    written: Vec<(SaveDataVar, usize)>,
+    overlap: Option<SaveError>,
+}
+
+impl<'a> SlotWriter<'a> {
+    fn new(raw: &'a [u8]) -> SlotWriter<'a> {
+        SlotWriter {
+            raw,
+            bytes: raw.to_vec(),
+            changed_by: vec![None; raw.len()],
+            written: vec![],
+            overlap: None,
+        }
+    }
+
+    /// Writes the bytes of the value that aren't the ones it was decoded from, the first time a
+    /// byte is changed by two variables is kept to fail `finish`.
+    fn write_bytes(&mut self, var: SaveDataVar, value_bytes: &[u8]) {
+        let offset = var.field().offset as usize;
+        self.written.push((var, value_bytes.len()));
+        for (i, byte) in value_bytes.iter().enumerate() {
+            let address = offset + i;
+            if self.raw[address] == *byte {
+                continue;
+            }
+            match self.changed_by[address] {
+                Some(other) if other != var => {
+                    self.overlap
+                        .get_or_insert(SaveError::OverlappingChanges { var, other });
+                }
+                _ => {
+                    self.bytes[address] = *byte;
+                    self.changed_by[address] = Some(var);
+                }
+            }
+        }
+    }
+
+    fn finish(self) -> Result<Vec<u8>, SaveError> {
+        match self.overlap {
+            Some(e) => Err(e),
+            None => Ok(self.bytes),
+        }
+    }
+
+    /// The game only checks for 0, any other byte already there is kept if it means the same.
+    fn write_bool(&mut self, var: SaveDataVar, value: bool) {
+        let byte = self.raw[var.field().offset as usize];
+        let byte = if (byte != 0) == value {
+            byte
+        } else {
+            value as u8
+        };
+        self.write_bytes(var, &[byte]);
+    }
+
+    fn write_u32(&mut self, var: SaveDataVar, value: u32) {
+        self.write_bytes(var, &value.to_le_bytes());
+    }
+
+    fn write_i32(&mut self, var: SaveDataVar, value: i32) {
+        self.write_bytes(var, &value.to_le_bytes());
+    }
+
+    fn write_i32_array(&mut self, var: SaveDataVar, values: &[i32]) {
+        let bytes: Vec<u8> = values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+        self.write_bytes(var, &bytes);
+    }
+
+    fn write_u8_array(&mut self, var: SaveDataVar, values: &[u8]) {
+        self.write_bytes(var, values);
+    }
+
+    fn write_figure_info_array(&mut self, var: SaveDataVar, values: &[SFigureDisplayInfo]) {
+        let bytes: Vec<u8> = values
+            .iter()
+            .flat_map(|value| {
+                let mut bytes = value.figure_id.to_le_bytes().to_vec();
+                bytes.extend(value.angle.to_le_bytes());
+                bytes
+            })
+            .collect();
+        self.write_bytes(var, &bytes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save_data_info::SAVE_FIELDS;
+
+    #[test]
+    fn encode_keeps_every_byte() {
+        let bytes: Vec<u8> = (0..SAVE_SLOT_SIZE as usize)
+            .map(|i| (i * 31 + 7) as u8)
+            .collect();
+        let slot_data = SlotData::decode(&bytes).unwrap();
+        assert_eq!(slot_data.encode().unwrap(), bytes);
+    }
+
+    #[test]
+    fn encode_writes_edited_values() {
+        let mut slot_data = SlotData::decode(&vec![0; SAVE_SLOT_SIZE as usize]).unwrap();
+        slot_data.medal_num = 7;
+        slot_data.score_list[2] = -1;
+        let bytes = slot_data.encode().unwrap();
+
+        let medal_offset = SaveDataVar::MedalNum.field().offset as usize;
+        assert_eq!(bytes[medal_offset..medal_offset + 4], 7i32.to_le_bytes());
+        let score_offset = SaveDataVar::ScoreList.field().offset as usize + 2 * 4;
+        assert_eq!(bytes[score_offset..score_offset + 4], (-1i32).to_le_bytes());
+        assert_eq!(SlotData::decode(&bytes).unwrap().medal_num, 7);
+    }
+
+    #[test]
+    fn fields_match_the_table() {
+        let slot_data = SlotData::decode(&vec![0; SAVE_SLOT_SIZE as usize]).unwrap();
+        let written = slot_data.write_all().written;
+        let vars: Vec<SaveDataVar> = written.iter().map(|(var, _)| *var).collect();
+        let table_vars: Vec<SaveDataVar> = SAVE_FIELDS.iter().map(|field| field.var).collect();
+        assert_eq!(vars, table_vars);
+        for (var, size) in written {
+            assert_eq!(size, var.field().int_type.size() as usize, "{:?}", var);
+        }
+    }
+
+    #[test]
+    fn overlapping_edits_are_kept_or_refused() {
+        let mut slot_data = SlotData::decode(&vec![0; SAVE_SLOT_SIZE as usize]).unwrap();
+        // the end of the time trial list is the start of the coop one in the table
+        slot_data.time_trial_list[39] = 6000;
+        let bytes = slot_data.encode().unwrap();
+        assert_eq!(SlotData::decode(&bytes).unwrap().time_trial_list[39], 6000);
+
+        let coop_start = SaveDataVar::TimeTrialCoopList.field().offset;
+        let list_start = SaveDataVar::TimeTrialList.field().offset;
+        let coop_index = 39 - (coop_start - list_start) as usize / 4;
+        slot_data.time_trial_coop_list[coop_index] = 7000;
+        assert!(matches!(
+            slot_data.encode(),
+            Err(SaveError::OverlappingChanges { .. })
+        ));
+    }
+
+    #[test]
+    fn decode_rejects_wrong_size() {
+        assert!(SlotData::decode(&[0; 16]).is_err());
+    }
+}
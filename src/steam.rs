@@ -0,0 +1,267 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::error::SaveError;
+
+/// Steam app id of PAC-MAN WORLD 2 Re-Pac, its Proton prefix is `compatdata/<id>`.
+pub const REPAC_APP_ID: &str = "2324290";
+
+/// How Steam was installed, Flatpak and Snap keep their data inside their own folders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SteamInstallKind {
+    Native,
+    Flatpak,
+    Snap,
+}
+
+impl SteamInstallKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SteamInstallKind::Native => "Steam",
+            SteamInstallKind::Flatpak => "Flatpak Steam",
+            SteamInstallKind::Snap => "Snap Steam",
+        }
+    }
+}
+
+/// Folders where Steam is usually installed on Linux, relative to the home folder, in the order
+/// they're checked.
+const STEAM_ROOTS_LINUX: [(SteamInstallKind, &str); 7] = [
+    (SteamInstallKind::Native, ".steam/steam"),
+    (SteamInstallKind::Native, ".local/share/Steam"),
+    (SteamInstallKind::Native, ".steam/root"),
+    (
+        SteamInstallKind::Flatpak,
+        ".var/app/com.valvesoftware.Steam/.local/share/Steam",
+    ),
+    (
+        SteamInstallKind::Flatpak,
+        ".var/app/com.valvesoftware.Steam/.steam/steam",
+    ),
+    (
+        SteamInstallKind::Snap,
+        "snap/steam/common/.local/share/Steam",
+    ),
+    (SteamInstallKind::Snap, "snap/steam/common/.steam/steam"),
+];
+
+/// A Steam library that has the Proton prefix of the game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepacPrefix {
+    pub kind: SteamInstallKind,
+    /// The `steamapps` folder of the library.
+    pub steamapps: PathBuf,
+}
+
+/// Reads the `"path"` of every library in a `libraryfolders.vdf` file. Every key is followed by
+/// its value or by a block, and only the paths right in a library's block are read. The file
+/// is read until the first token that doesn't fit.
+pub fn parse_library_folders(vdf: &str) -> Vec<PathBuf> {
+    let mut libraries = vec![];
+    // keys of the blocks around the current token, "libraryfolders" then the library number
+    let mut blocks: Vec<String> = vec![];
+    let mut key: Option<String> = None;
+    for token in vdf_tokens(vdf) {
+        match (token, key.take()) {
+            (VdfToken::Text(text), None) => key = Some(text),
+            (VdfToken::Text(value), Some(key)) => {
+                if blocks.len() == 2
+                    && blocks[0].eq_ignore_ascii_case("libraryfolders")
+                    && key.eq_ignore_ascii_case("path")
+                {
+                    libraries.push(PathBuf::from(value));
+                }
+            }
+            (VdfToken::Open, Some(key)) => blocks.push(key),
+            (VdfToken::Close, None) if blocks.pop().is_some() => {}
+            _ => break,
+        }
+    }
+    libraries
+}
+
+#[derive(Debug, PartialEq)]
+enum VdfToken {
+    Text(String),
+    Open,
+    Close,
+}
+
+/// Splits a Valve KeyValues file in its quoted strings and braces, comments are skipped.
+fn vdf_tokens(vdf: &str) -> Vec<VdfToken> {
+    let mut tokens = vec![];
+    let mut chars = vdf.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => tokens.push(VdfToken::Open),
+            '}' => tokens.push(VdfToken::Close),
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '"' => {
+                let mut text = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => {
+                            if let Some(escaped) = chars.next() {
+                                text.push(match escaped {
+                                    'n' => '\n',
+                                    't' => '\t',
+                                    other => other,
+                                });
+                            }
+                        }
+                        _ => text.push(c),
+                    }
+                }
+                tokens.push(VdfToken::Text(text));
+            }
+            _ => {}
+        }
+    }
+    tokens
+}
+
+/// The Steam libraries listed by the Steam install at `steam_root`, including the root itself.
+pub fn steam_libraries(steam_root: &Path) -> Vec<PathBuf> {
+    let mut libraries = vec![steam_root.to_path_buf()];
+    for vdf_path in [
+        steam_root.join("steamapps").join("libraryfolders.vdf"),
+        steam_root.join("config").join("libraryfolders.vdf"),
+    ] {
+        let Ok(vdf) = fs::read_to_string(&vdf_path) else {
+            continue;
+        };
+        for library in parse_library_folders(&vdf) {
+            if !libraries.contains(&library) {
+                libraries.push(library);
+            }
+        }
+    }
+    libraries
+}
+
+/// The Steam roots that can exist in `home_dir`, in the order they're checked.
+pub fn steam_root_candidates_linux(home_dir: &Path) -> Vec<(SteamInstallKind, PathBuf)> {
+    STEAM_ROOTS_LINUX
+        .iter()
+        .map(|(kind, root)| (*kind, home_dir.join(root)))
+        .collect()
+}
+
+/// Every Steam library with the Proton prefix of the game, from every known Steam root.
+/// Roots that are links to the same folder only show up once.
+pub fn find_repac_prefixes_linux() -> Result<Vec<RepacPrefix>, SaveError> {
+    let home_dir = dirs::home_dir()
+        .ok_or_else(|| SaveError::PathNotFound("the home folder could not be found".to_string()))?;
+
+    let mut prefixes: Vec<RepacPrefix> = vec![];
+    let mut seen_prefixes: Vec<PathBuf> = vec![];
+    let mut searched = vec![];
+    for (kind, steam_root) in steam_root_candidates_linux(&home_dir) {
+        if !steam_root.is_dir() {
+            continue;
+        }
+        for library in steam_libraries(&steam_root) {
+            let steamapps = library.join("steamapps");
+            let compatdata = steamapps.join("compatdata").join(REPAC_APP_ID);
+            let Ok(real_compatdata) = fs::canonicalize(&compatdata) else {
+                searched.push(steamapps.display().to_string());
+                continue;
+            };
+            if !seen_prefixes.contains(&real_compatdata) {
+                seen_prefixes.push(real_compatdata);
+                prefixes.push(RepacPrefix { kind, steamapps });
+            }
+        }
+    }
+
+    if !prefixes.is_empty() {
+        Ok(prefixes)
+    } else if searched.is_empty() {
+        Err(SaveError::PathNotFound(
+            "no Steam install found in the home folder".to_string(),
+        ))
+    } else {
+        Err(SaveError::PathNotFound(format!(
+            "no Steam library has the game's Proton prefix, searched in {}",
+            searched.join(", ")
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn native_roots_are_checked_first() {
+        let candidates = steam_root_candidates_linux(Path::new("/home/pac"));
+        assert_eq!(candidates[0].0, SteamInstallKind::Native);
+        assert!(
+            candidates
+                .iter()
+                .any(|(kind, root)| *kind == SteamInstallKind::Flatpak
+                    && root.starts_with("/home/pac/.var/app/com.valvesoftware.Steam"))
+        );
+        assert!(
+            candidates
+                .iter()
+                .any(|(kind, root)| *kind == SteamInstallKind::Snap
+                    && root.starts_with("/home/pac/snap/steam"))
+        );
+        assert!(
+            candidates
+                .windows(2)
+                .all(|pair| pair[0].0 as u8 <= pair[1].0 as u8)
+        );
+    }
+
+    #[test]
+    fn library_paths_are_read() {
+        let vdf = r#"
+"libraryfolders"
+{
+	"0"
+	{
+		"path"		"/home/pac/.local/share/Steam"
+		"label"		"path"
+		"apps"
+		{
+			"228980"		"1234"
+			"path"		"/not/a/library"
+		}
+	}
+	// second drive
+	"1"
+	{
+		"path"		"/mnt/games/SteamLibrary"
+		"apps"
+		{
+			"2324290"		"5678"
+		}
+	}
+	"2"
+	{
+		"path"		"D:\\SteamLibrary"
+	}
+	"path"		"/not/a/library/either"
+}
+"#;
+        assert_eq!(
+            parse_library_folders(vdf),
+            vec![
+                PathBuf::from("/home/pac/.local/share/Steam"),
+                PathBuf::from("/mnt/games/SteamLibrary"),
+                PathBuf::from("D:\\SteamLibrary"),
+            ]
+        );
+    }
+}
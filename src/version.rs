@@ -0,0 +1,112 @@
+use std::{fmt, ops::Range};
+
+use crate::{
+    error::SaveError,
+    save_data_info::{
+        FieldCategory, SAVE_FIELDS, SAVE_SLOT_BASE_ADDRESSES, SAVE_SLOT_SIZE, SaveDataVar,
+    },
+};
+
+/// Game update that wrote a save file, later versions only add variables to the slots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum SaveVersion {
+    Original,
+    #[default]
+    SonicUpdate,
+}
+
+impl SaveVersion {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SaveVersion::Original => "Original Release",
+            SaveVersion::SonicUpdate => "Sonic Update",
+        }
+    }
+}
+
+impl fmt::Display for SaveVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl SaveDataVar {
+    /// First version of the game that has this variable in its saves.
+    pub fn min_version(self) -> SaveVersion {
+        match self.field().category {
+            FieldCategory::Sonic => SaveVersion::SonicUpdate,
+            _ => SaveVersion::Original,
+        }
+    }
+}
+
+/// Offsets of the slot that only the Sonic update uses, from its first variable to the end of
+/// its last one.
+pub fn sonic_update_range() -> Range<u32> {
+    let sonic_fields = SAVE_FIELDS
+        .iter()
+        .filter(|field| field.var.min_version() == SaveVersion::SonicUpdate);
+    let start = sonic_fields
+        .clone()
+        .map(|field| field.offset)
+        .min()
+        .unwrap_or(SAVE_SLOT_SIZE);
+    let end = sonic_fields
+        .map(|field| field.offset + field.int_type.size())
+        .max()
+        .unwrap_or(SAVE_SLOT_SIZE);
+    start..end
+}
+
+/// Fails with a variable that `version` doesn't have and that is different between the bytes of
+/// the two slots. Some variables overlap, the smallest one is named as it's the most likely to be
+/// the one that changed.
+pub fn check_slot_version(
+    version: SaveVersion,
+    old_slot: &[u8],
+    new_slot: &[u8],
+) -> Result<(), SaveError> {
+    let changed = SAVE_FIELDS
+        .iter()
+        .filter(|field| field.var.min_version() > version)
+        .filter(|field| {
+            let range = field.offset as usize..(field.offset + field.int_type.size()) as usize;
+            old_slot.get(range.clone()) != new_slot.get(range)
+        })
+        .min_by_key(|field| field.int_type.size());
+    match changed {
+        Some(field) => Err(SaveError::NotInVersion {
+            var: field.var,
+            version,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Guesses the version from the slots in use: the Sonic update sets some of its variables when
+/// creating a save, so an older game leaves that part of every slot as 0.
+/// Files without any slot in use are taken as the latest version.
+pub fn detect_version(save_data: &[u8]) -> SaveVersion {
+    let sonic_range = sonic_update_range();
+    let mut any_slot_exists = false;
+    for base in SAVE_SLOT_BASE_ADDRESSES {
+        let exists = save_data
+            .get((base + SaveDataVar::FileExists.field().offset) as usize)
+            .is_some_and(|byte| *byte != 0);
+        if !exists {
+            continue;
+        }
+        any_slot_exists = true;
+        let sonic_bytes = save_data
+            .get((base + sonic_range.start) as usize..(base + sonic_range.end) as usize)
+            .unwrap_or_default();
+        if sonic_bytes.iter().any(|byte| *byte != 0) {
+            return SaveVersion::SonicUpdate;
+        }
+    }
+    if any_slot_exists {
+        SaveVersion::Original
+    } else {
+        SaveVersion::SonicUpdate
+    }
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use common::{TempDir, new_save_image};
+use pw2repac_save::{
+    SaveFile,
+    backup::{Backup, create_backup, diff_save_files, list_backups, prune_backups},
+    layout::SaveSection,
+    save_data_info::SaveDataVar,
+};
+
+#[test]
+fn backups_are_kept_compared_and_restored() {
+    let folder = TempDir::new("backups");
+    let backups_folder = folder.join("backups");
+    let path = folder.join("DAT00000.dat");
+    std::fs::write(&path, new_save_image()).unwrap();
+
+    let mut save_file = SaveFile::from_path(&path).unwrap();
+    for lives in 1..=3 {
+        assert!(create_backup(&path, &backups_folder, 2).unwrap().is_some());
+        save_file
+            .slot_mut(1)
+            .set_int(SaveDataVar::Lives, lives)
+            .unwrap();
+        save_file.write().unwrap();
+    }
+    assert!(create_backup(&path, &backups_folder, 0).unwrap().is_none());
+
+    // only the 2 newest are kept, the ones taken before writing 2 and 3 lives
+    let backups = list_backups(&backups_folder).unwrap();
+    assert_eq!(backups.len(), 2);
+    assert!(backups[0].summary[1].starts_with("Save 1: 2 lives"));
+    assert!(backups[1].summary[1].starts_with("Save 1: 1 lives"));
+
+    let backup_file = backups[1].save_file().unwrap();
+    let diff = diff_save_files(backup_file.as_bytes(), save_file.as_bytes());
+    assert_eq!(diff.len(), 1);
+    assert_eq!(diff[0].section, SaveSection::Slot(1));
+    // arrays with a too long length in the table can overlap it too
+    assert!(diff[0].vars.contains(&SaveDataVar::Lives));
+
+    save_file.restore_slot(&backup_file, 1).unwrap();
+    assert_eq!(save_file.slot(1).get_int(SaveDataVar::Lives).unwrap(), 1);
+    assert_eq!(save_file.modified_slots(), vec![1]);
+    save_file.restore(&new_save_image()).unwrap();
+    assert!(save_file.as_bytes() == new_save_image().as_slice());
+}
+
+#[test]
+fn backups_are_sorted_by_their_date() {
+    let folder = TempDir::new("backups-sorted");
+    let names = [
+        "2025-10-26_00-30-00_UTC_09",
+        "2025-10-26_00-30-00_UTC_10",
+        "2025-10-26_01-15-00_UTC_01",
+        "2025-10-25_23-59-59_UTC_100",
+    ];
+    for name in names {
+        std::fs::write(folder.join(format!("{}.dat", name)), []).unwrap();
+    }
+
+    let stems = |backups: Vec<Backup>| -> Vec<String> {
+        backups
+            .iter()
+            .map(|backup| {
+                backup
+                    .path
+                    .file_stem()
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect()
+    };
+    assert_eq!(
+        stems(list_backups(folder.path()).unwrap()),
+        [names[2], names[1], names[0], names[3]]
+    );
+    prune_backups(folder.path(), 2).unwrap();
+    assert_eq!(
+        stems(list_backups(folder.path()).unwrap()),
+        [names[2], names[1]]
+    );
+}
@@ -0,0 +1,104 @@
+mod common;
+
+use std::{path::Path, process::Command};
+
+use common::TempDir;
+use pw2repac_save::{SaveFile, save_data_info::SaveDataVar, save_file_parser::SAVE_PATH_ENV_VAR};
+
+fn cli(args: &[&str]) -> (bool, String) {
+    cli_with_env(args, &[])
+}
+
+fn cli_with_env(args: &[&str], vars: &[(&str, &Path)]) -> (bool, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_pw2repac-cli"))
+        .args(args)
+        .env_remove(SAVE_PATH_ENV_VAR)
+        .env_remove("LOCALAPPDATA")
+        .envs(vars.iter().copied())
+        .output()
+        .unwrap();
+    (
+        output.status.success(),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+#[test]
+fn slots_are_edited_from_the_command_line() {
+    let folder = TempDir::new("cli");
+    let path = folder.join("DAT00000.dat");
+    std::fs::write(&path, SaveFile::empty().as_bytes()).unwrap();
+    let path = path.to_str().unwrap();
+    let options = ["--path", path, "--backups", "0", "--force"];
+    let run = |args: &[&str]| cli(&[options.as_slice(), args].concat());
+
+    assert!(run(&["create-slot", "2"]).0);
+    assert!(run(&["--slot", "2", "set", "Lives", "9"]).0);
+    assert!(run(&["--slot", "2", "set", "m_iStageScoreList", "3", "1234"]).0);
+    assert_eq!(
+        run(&["--slot", "2", "get", "m_iStockNum"]),
+        (true, "9\n".to_string())
+    );
+    assert_eq!(
+        run(&["--slot", "2", "get", "ScoreList", "3"]),
+        (true, "1234\n".to_string())
+    );
+    assert!(
+        run(&["--slot", "2", "dump", "--simple"])
+            .1
+            .contains("\nLives = 9\n")
+    );
+    // the global data has no code names, its simple names are printed instead
+    assert!(run(&["dump"]).1.contains("Last Slot Used = "));
+    assert!(!run(&["get", "(unknown)"]).0);
+    // slot variables need a slot, and arrays an index in range
+    assert!(!run(&["set", "Lives", "9"]).0);
+    assert!(!run(&["--slot", "2", "set", "ScoreList", "1000", "1"]).0);
+
+    let save_file = SaveFile::from_path(path).unwrap();
+    assert!(save_file.slot(2).exists().unwrap());
+    assert_eq!(save_file.slot(2).get_int(SaveDataVar::Lives).unwrap(), 9);
+    assert_eq!(
+        save_file
+            .slot(2)
+            .get_int_array_value(SaveDataVar::ScoreList, 3)
+            .unwrap(),
+        1234
+    );
+
+    assert!(run(&["delete-slot", "2"]).0);
+    assert_eq!(
+        run(&["--slot", "2", "get", "FileExists"]),
+        (true, "0\n".to_string())
+    );
+}
+
+#[test]
+fn found_saves_only_print_the_value() {
+    let folder = TempDir::new("cli-found");
+    let account_folder = folder
+        .join("BANDAI NAMCO Entertainment")
+        .join("PAC-MAN WORLD2 Re-Pac")
+        .join("Saved")
+        .join("SaveGames")
+        .join("76561198000000000");
+    std::fs::create_dir_all(&account_folder).unwrap();
+    let path = account_folder.join("DAT00000.dat");
+    let mut save_file = SaveFile::empty();
+    save_file.slot_mut(1).create().unwrap();
+    save_file
+        .slot_mut(1)
+        .set_int(SaveDataVar::Lives, 7)
+        .unwrap();
+    std::fs::write(&path, save_file.as_bytes()).unwrap();
+
+    let get_lives = ["--slot", "1", "get", "Lives"];
+    assert_eq!(
+        cli_with_env(&get_lives, &[(SAVE_PATH_ENV_VAR, &path)]),
+        (true, "7\n".to_string())
+    );
+    assert_eq!(
+        cli_with_env(&get_lives, &[("LOCALAPPDATA", folder.path())]),
+        (true, "7\n".to_string())
+    );
+}
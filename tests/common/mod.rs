@@ -0,0 +1,49 @@
+// every test file uses a part of it
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+
+use pw2repac_save::{
+    EXPECTED_SAVE_FILE_SIZE, new_file::get_new_save_file, save_data_info::get_save_slot_base_add,
+};
+
+/// A folder for the files of one test, deleted when the test ends, even when it fails.
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// `name` has to be different for every test, they run at the same time.
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("pw2repac-{}-{}", name, std::process::id()));
+        // left over from a test run that was killed
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        TempDir { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn join(&self, path: impl AsRef<Path>) -> PathBuf {
+        self.path.join(path)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+/// A whole save file with a brand new save in every slot.
+pub fn new_save_image() -> Vec<u8> {
+    let mut save_data = vec![0; EXPECTED_SAVE_FILE_SIZE];
+    let new_save_file = get_new_save_file();
+    for slot in 1..=4 {
+        let start = get_save_slot_base_add(slot).unwrap() as usize;
+        save_data[start..start + new_save_file.len()].copy_from_slice(&new_save_file);
+    }
+    save_data
+}
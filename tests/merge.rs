@@ -0,0 +1,96 @@
+mod common;
+
+use std::path::Path;
+
+use common::{TempDir, new_save_image};
+use pw2repac_save::{
+    SaveFile, SaveVersion,
+    layout::SaveSection,
+    merge::{MergeItem, MergeSide},
+    save_data_info::SaveDataVar,
+};
+
+/// Writes the file with a newer date, like the game saving it.
+fn write_like_the_game(path: &Path, data: &[u8]) {
+    let game_file = std::fs::File::create(path).unwrap();
+    std::io::Write::write_all(&mut &game_file, data).unwrap();
+    // make sure the date changes even on file systems with a coarse one
+    game_file
+        .set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(10))
+        .unwrap();
+}
+
+#[test]
+fn changes_on_disk_are_merged() {
+    let folder = TempDir::new("merge");
+    let path = folder.join("DAT00000.dat");
+    std::fs::write(&path, new_save_image()).unwrap();
+
+    let mut save_file = SaveFile::from_path(&path).unwrap();
+    assert!(save_file.read_disk_changes().unwrap().is_none());
+    save_file
+        .slot_mut(1)
+        .set_int(SaveDataVar::Lives, 9)
+        .unwrap();
+    save_file
+        .slot_mut(1)
+        .set_int(SaveDataVar::MedalNum, 5)
+        .unwrap();
+
+    // the game saves slot 1 and 2 meanwhile
+    let mut game_save = SaveFile::from_bytes(new_save_image()).unwrap();
+    game_save
+        .slot_mut(1)
+        .set_int(SaveDataVar::Lives, 3)
+        .unwrap();
+    game_save
+        .slot_mut(2)
+        .set_int(SaveDataVar::Lives, 7)
+        .unwrap();
+    write_like_the_game(&path, game_save.as_bytes());
+
+    let disk = save_file.read_disk_changes().unwrap().unwrap();
+    let mut items = save_file.disk_merge_items(&disk);
+    let lives_item = |slot| {
+        move |item: &&mut MergeItem| {
+            item.section == SaveSection::Slot(slot) && item.vars.contains(&SaveDataVar::Lives)
+        }
+    };
+    let slot_1_lives = items.iter_mut().find(lives_item(1)).unwrap();
+    assert!(slot_1_lives.is_conflict());
+    assert_eq!(slot_1_lives.side, MergeSide::Editor);
+    slot_1_lives.side = MergeSide::Disk;
+    let slot_2_lives = items.iter_mut().find(lives_item(2)).unwrap();
+    assert!(!slot_2_lives.is_conflict());
+    assert_eq!(slot_2_lives.side, MergeSide::Disk);
+
+    save_file.merge_disk_changes(disk, &items);
+    assert_eq!(save_file.slot(1).get_int(SaveDataVar::Lives).unwrap(), 3);
+    assert_eq!(save_file.slot(1).get_int(SaveDataVar::MedalNum).unwrap(), 5);
+    assert_eq!(save_file.slot(2).get_int(SaveDataVar::Lives).unwrap(), 7);
+    // what the game wrote is the new base, only the medals are still an edit
+    assert_eq!(save_file.modified_slots(), vec![1]);
+    assert!(save_file.read_disk_changes().unwrap().is_none());
+}
+
+#[test]
+fn reloads_keep_the_picked_version() {
+    let folder = TempDir::new("merge-version");
+    let path = folder.join("DAT00000.dat");
+    std::fs::write(&path, new_save_image()).unwrap();
+
+    let mut save_file = SaveFile::from_path(&path).unwrap();
+    assert_eq!(save_file.version(), SaveVersion::SonicUpdate);
+    save_file.set_version(SaveVersion::Original);
+
+    let mut game_save = SaveFile::from_bytes(new_save_image()).unwrap();
+    game_save
+        .slot_mut(2)
+        .set_int(SaveDataVar::Lives, 7)
+        .unwrap();
+    write_like_the_game(&path, game_save.as_bytes());
+
+    let disk = save_file.read_disk_changes().unwrap().unwrap();
+    assert_eq!(disk.version(), SaveVersion::Original);
+    assert_eq!(disk.version_override(), Some(SaveVersion::Original));
+}
@@ -0,0 +1,506 @@
+mod common;
+
+use common::new_save_image;
+use pw2repac_save::{
+    EXPECTED_SAVE_FILE_SIZE, SaveError, SaveFile, SaveVersion,
+    new_file::get_new_save_file,
+    save_data_info::{
+        SAVE_FIELDS, SYSTEM_AREA_START, SaveDataIntType, SaveDataVar, SaveFileData,
+        get_save_slot_base_add,
+    },
+    save_file_parser::{
+        SFigureDisplayInfo, get_figure_info_from_save_data, get_figure_info_value_from_save_data,
+        get_float_value_from_save_data, get_int_array_from_save_data, get_int_value_from_save_data,
+        get_text_value_from_save_data, modify_figure_info, modify_save_data,
+        modify_save_data_float, modify_save_data_text,
+    },
+    version::{detect_version, sonic_update_range},
+};
+
+fn all_vars() -> impl Iterator<Item = SaveFileData> {
+    (1..=4).flat_map(|slot| {
+        SAVE_FIELDS
+            .iter()
+            .map(move |field| field.at_slot(slot).unwrap())
+    })
+}
+
+/// Size of one element of the variable and how many elements it has.
+fn element_layout(int_type: SaveDataIntType) -> (u32, u32) {
+    match int_type {
+        SaveDataIntType::Bool => (1, 1),
+        SaveDataIntType::U32
+        | SaveDataIntType::I32
+        | SaveDataIntType::F32
+        | SaveDataIntType::Bitfield => (4, 1),
+        SaveDataIntType::Arrayi32(len) | SaveDataIntType::BitfieldArray(len) => (4, len),
+        SaveDataIntType::Arrayu8(len) | SaveDataIntType::ArrayText(len) => (1, len),
+        SaveDataIntType::SFigureDisplayInfoArray(len) => (8, len),
+    }
+}
+
+/// Every byte that differs between the two images.
+fn changed_addresses(before: &[u8], after: &[u8]) -> Vec<usize> {
+    before
+        .iter()
+        .zip(after)
+        .enumerate()
+        .filter(|(_, (a, b))| a != b)
+        .map(|(address, _)| address)
+        .collect()
+}
+
+/// Reads every value of the variable and writes it back.
+fn write_back(save_data: &mut [u8], var_data: &SaveFileData) -> Result<(), SaveError> {
+    let (element_size, _) = element_layout(var_data.int_type);
+    match var_data.int_type {
+        SaveDataIntType::Bool
+        | SaveDataIntType::U32
+        | SaveDataIntType::I32
+        | SaveDataIntType::Bitfield => {
+            let value = get_int_value_from_save_data(
+                save_data,
+                var_data.slot_base_add,
+                var_data.offset,
+                &var_data.int_type,
+            )?;
+            modify_save_data(
+                save_data,
+                var_data.slot_base_add,
+                var_data.offset,
+                var_data.int_type,
+                value,
+            )
+        }
+        SaveDataIntType::F32 => {
+            let value = get_float_value_from_save_data(
+                save_data,
+                var_data.slot_base_add,
+                var_data.offset,
+                &var_data.int_type,
+            )?;
+            modify_save_data_float(save_data, var_data.slot_base_add, var_data.offset, value)
+        }
+        SaveDataIntType::Arrayi32(_)
+        | SaveDataIntType::Arrayu8(_)
+        | SaveDataIntType::BitfieldArray(_) => {
+            let values = get_int_array_from_save_data(
+                save_data,
+                var_data.slot_base_add,
+                var_data.offset,
+                &var_data.int_type,
+            )?;
+            for (i, value) in values.into_iter().enumerate() {
+                modify_save_data(
+                    save_data,
+                    var_data.slot_base_add,
+                    var_data.offset + i as u32 * element_size,
+                    var_data.int_type,
+                    value,
+                )?;
+            }
+            Ok(())
+        }
+        SaveDataIntType::SFigureDisplayInfoArray(len) => {
+            let figure_info = get_figure_info_from_save_data(
+                save_data,
+                var_data.slot_base_add,
+                var_data.offset,
+                len,
+            )?;
+            for (i, info) in figure_info.into_iter().enumerate() {
+                modify_figure_info(
+                    save_data,
+                    var_data.slot_base_add,
+                    var_data.offset,
+                    var_data.int_type,
+                    i,
+                    info,
+                )?;
+            }
+            Ok(())
+        }
+        SaveDataIntType::ArrayText(_) => {
+            let text = get_text_value_from_save_data(
+                save_data,
+                var_data.slot_base_add,
+                var_data.offset,
+                &var_data.int_type,
+            )?;
+            modify_save_data_text(
+                save_data,
+                var_data.slot_base_add,
+                var_data.offset,
+                var_data.int_type,
+                &text,
+            )
+        }
+    }
+}
+
+#[test]
+fn new_save_file_fits_in_a_slot() {
+    let new_save_file = get_new_save_file();
+    assert_eq!(
+        new_save_file.len() as u32,
+        get_save_slot_base_add(2).unwrap() - get_save_slot_base_add(1).unwrap()
+    );
+}
+
+#[test]
+fn writing_back_every_value_keeps_the_image() {
+    let original = new_save_image();
+    let mut save_data = original.clone();
+
+    for var_data in all_vars() {
+        write_back(&mut save_data, &var_data).unwrap_or_else(|e| {
+            panic!(
+                "{:?} of slot base {:X}: {}",
+                var_data.var, var_data.slot_base_add, e
+            )
+        });
+        assert!(
+            save_data == original,
+            "writing back {:?} at {:X} changed bytes {:X?}",
+            var_data.var,
+            var_data.slot_base_add + var_data.offset,
+            changed_addresses(&original, &save_data)
+        );
+    }
+}
+
+#[test]
+fn text_is_not_writable() {
+    let mut save_data = new_save_image();
+    for var_data in all_vars() {
+        if let SaveDataIntType::ArrayText(_) = var_data.int_type {
+            let res = modify_save_data(
+                &mut save_data,
+                var_data.slot_base_add,
+                var_data.offset,
+                var_data.int_type,
+                0,
+            );
+            assert!(matches!(res, Err(SaveError::UnsupportedType { .. })));
+        }
+    }
+    assert!(changed_addresses(&new_save_image(), &save_data).is_empty());
+}
+
+#[test]
+fn edits_only_touch_their_own_bytes() {
+    let original = new_save_image();
+    let mut save_data = original.clone();
+
+    for var_data in all_vars() {
+        let (element_size, len) = element_layout(var_data.int_type);
+        if let SaveDataIntType::ArrayText(_) = var_data.int_type {
+            // the date takes the whole text
+            let text = "2025/10/26 01:30";
+            let address = (var_data.slot_base_add + var_data.offset) as usize;
+            let written = address..address + len as usize;
+            modify_save_data_text(
+                &mut save_data,
+                var_data.slot_base_add,
+                var_data.offset,
+                var_data.int_type,
+                text,
+            )
+            .unwrap();
+            assert_eq!(
+                &save_data[written.clone()],
+                text.as_bytes(),
+                "{:?}",
+                var_data.var
+            );
+            assert!(
+                save_data[..written.start] == original[..written.start]
+                    && save_data[written.end..] == original[written.end..],
+                "{:?} at {:X} changed bytes {:X?}",
+                var_data.var,
+                address,
+                changed_addresses(&original, &save_data)
+            );
+            save_data[written.clone()].copy_from_slice(&original[written]);
+            continue;
+        }
+        let written_size = element_size as usize;
+
+        for index in [0, len - 1] {
+            let offset = var_data.offset + index * element_size;
+            let address = (var_data.slot_base_add + offset) as usize;
+            let written = address..address + written_size;
+
+            let old_value = original[address];
+            let new_value = match var_data.int_type {
+                SaveDataIntType::Bool => (old_value == 0) as i64,
+                _ if old_value == 1 => 2,
+                _ => 1,
+            };
+            match var_data.int_type {
+                SaveDataIntType::F32 => modify_save_data_float(
+                    &mut save_data,
+                    var_data.slot_base_add,
+                    offset,
+                    new_value as f32,
+                ),
+                SaveDataIntType::SFigureDisplayInfoArray(_) => modify_figure_info(
+                    &mut save_data,
+                    var_data.slot_base_add,
+                    var_data.offset,
+                    var_data.int_type,
+                    index as usize,
+                    SFigureDisplayInfo {
+                        figure_id: new_value as i32,
+                        angle: new_value as f32,
+                    },
+                ),
+                _ => modify_save_data(
+                    &mut save_data,
+                    var_data.slot_base_add,
+                    offset,
+                    var_data.int_type,
+                    new_value,
+                ),
+            }
+            .unwrap();
+
+            assert_ne!(
+                save_data[written.clone()],
+                original[written.clone()],
+                "{:?} [{}] wrote nothing at {:X}",
+                var_data.var,
+                index,
+                address
+            );
+            assert!(
+                save_data[..written.start] == original[..written.start]
+                    && save_data[written.end..] == original[written.end..],
+                "{:?} [{}] at {:X} changed bytes {:X?}",
+                var_data.var,
+                index,
+                address,
+                changed_addresses(&original, &save_data)
+            );
+            save_data[written.clone()].copy_from_slice(&original[written]);
+        }
+    }
+}
+
+#[test]
+fn edits_are_read_back() {
+    let mut save_data = new_save_image();
+
+    for var_data in all_vars() {
+        let value = match var_data.int_type {
+            SaveDataIntType::Bool => 1,
+            SaveDataIntType::U32 | SaveDataIntType::I32 => 12345,
+            SaveDataIntType::Bitfield => 0x8000_0001,
+            _ => continue,
+        };
+        modify_save_data(
+            &mut save_data,
+            var_data.slot_base_add,
+            var_data.offset,
+            var_data.int_type,
+            value,
+        )
+        .unwrap();
+        let read_value = get_int_value_from_save_data(
+            &save_data,
+            var_data.slot_base_add,
+            var_data.offset,
+            &var_data.int_type,
+        )
+        .unwrap();
+        assert_eq!(read_value, value, "{:?}", var_data.var);
+    }
+}
+
+#[test]
+fn figure_id_and_angle_are_edited_separately() {
+    let mut save_data = new_save_image();
+    let var_data = SAVE_FIELDS
+        .iter()
+        .find(|field| matches!(field.int_type, SaveDataIntType::SFigureDisplayInfoArray(_)))
+        .unwrap()
+        .at_slot(2)
+        .unwrap();
+    let read = |save_data: &[u8], index| {
+        get_figure_info_value_from_save_data(
+            save_data,
+            var_data.slot_base_add,
+            var_data.offset,
+            &var_data.int_type,
+            index,
+        )
+        .unwrap()
+    };
+
+    let info = read(&save_data, 3);
+    let with_id = SFigureDisplayInfo {
+        figure_id: 12,
+        ..info
+    };
+    modify_figure_info(
+        &mut save_data,
+        var_data.slot_base_add,
+        var_data.offset,
+        var_data.int_type,
+        3,
+        with_id,
+    )
+    .unwrap();
+    assert_eq!(read(&save_data, 3), with_id);
+
+    let with_angle = SFigureDisplayInfo {
+        angle: 90.5,
+        ..with_id
+    };
+    modify_figure_info(
+        &mut save_data,
+        var_data.slot_base_add,
+        var_data.offset,
+        var_data.int_type,
+        3,
+        with_angle,
+    )
+    .unwrap();
+    assert_eq!(read(&save_data, 3), with_angle);
+    assert_eq!(read(&save_data, 2), read(&new_save_image(), 2));
+    assert_eq!(read(&save_data, 4), read(&new_save_image(), 4));
+
+    assert!(
+        modify_save_data(
+            &mut save_data,
+            var_data.slot_base_add,
+            var_data.offset,
+            var_data.int_type,
+            1,
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn new_save_values_have_names() {
+    let save_data = new_save_image();
+    for var_data in all_vars() {
+        let Some(values) = var_data.var.field().values else {
+            continue;
+        };
+        let value = get_int_value_from_save_data(
+            &save_data,
+            var_data.slot_base_add,
+            var_data.offset,
+            &var_data.int_type,
+        )
+        .unwrap();
+        assert!(
+            values.iter().any(|(known_value, _)| *known_value == value),
+            "{:?} is {} which has no name",
+            var_data.var,
+            value
+        );
+    }
+}
+
+#[test]
+fn system_area_changes_are_tracked() {
+    let mut save_file = SaveFile::from_bytes(new_save_image()).unwrap();
+    assert!(save_file.system_area_changes().is_empty());
+
+    save_file.as_bytes_mut()[SYSTEM_AREA_START as usize + 0x20] ^= 0xFF;
+    assert_eq!(save_file.system_area_changes(), vec![0x20]);
+    assert!(save_file.modified_slots().is_empty());
+
+    save_file.revert_system_area();
+    assert!(save_file.system_area_changes().is_empty());
+    assert!(save_file.as_bytes() == new_save_image().as_slice());
+}
+
+/// The new save image with the variables of the Sonic update cleared, like an older game writes.
+fn original_save_image() -> Vec<u8> {
+    let mut save_data = new_save_image();
+    let sonic_range = sonic_update_range();
+    for slot in 1..=4 {
+        let base = get_save_slot_base_add(slot).unwrap();
+        save_data[(base + sonic_range.start) as usize..(base + sonic_range.end) as usize].fill(0);
+    }
+    save_data
+}
+
+#[test]
+fn version_is_detected_from_the_slots() {
+    assert_eq!(detect_version(&new_save_image()), SaveVersion::SonicUpdate);
+    assert_eq!(
+        detect_version(&original_save_image()),
+        SaveVersion::Original
+    );
+    assert_eq!(
+        detect_version(&vec![0; EXPECTED_SAVE_FILE_SIZE]),
+        SaveVersion::SonicUpdate
+    );
+}
+
+#[test]
+fn picked_version_is_kept() {
+    let mut save_file = SaveFile::from_bytes(original_save_image()).unwrap();
+    save_file.set_version(SaveVersion::SonicUpdate);
+    save_file.restore(&original_save_image()).unwrap();
+    assert_eq!(save_file.version(), SaveVersion::SonicUpdate);
+    save_file
+        .slot_mut(1)
+        .set_int(SaveDataVar::JukeBoxBGMCollab, 86)
+        .unwrap();
+}
+
+#[test]
+fn header_variables_are_not_in_slots() {
+    let mut save_file = SaveFile::from_bytes(new_save_image()).unwrap();
+    let res = save_file.slot_mut(2).set_int(SaveDataVar::HeaderVersion, 1);
+    assert!(matches!(res, Err(SaveError::NotInSlot { .. })));
+    assert!(
+        save_file
+            .slot(2)
+            .get_int(SaveDataVar::LastSlotUsed)
+            .is_err()
+    );
+    assert_eq!(save_file.as_bytes(), new_save_image().as_slice());
+}
+
+#[test]
+fn older_saves_refuse_newer_variables() {
+    let mut save_file = SaveFile::from_bytes(original_save_image()).unwrap();
+    assert_eq!(save_file.version(), SaveVersion::Original);
+
+    let res = save_file
+        .slot_mut(1)
+        .set_int(SaveDataVar::JukeBoxBGMCollab, 86);
+    assert!(matches!(res, Err(SaveError::NotInVersion { .. })));
+    save_file
+        .slot_mut(1)
+        .set_int(SaveDataVar::Lives, 9)
+        .unwrap();
+
+    let mut slot_data = save_file.slot(2).data().unwrap();
+    slot_data.juke_box_bgm_collab = 86;
+    let res = save_file.slot_mut(2).set_data(&slot_data);
+    assert!(matches!(
+        res,
+        Err(SaveError::NotInVersion {
+            var: SaveDataVar::JukeBoxBGMCollab,
+            ..
+        })
+    ));
+
+    // a slot of the Sonic update can't be restored over one of the original game either
+    let sonic_save_file = SaveFile::from_bytes(new_save_image()).unwrap();
+    let res = save_file.restore_slot(&sonic_save_file, 4);
+    assert!(matches!(res, Err(SaveError::NotInVersion { .. })));
+
+    save_file.slot_mut(3).delete().unwrap();
+    save_file.slot_mut(3).create().unwrap();
+    assert_eq!(save_file.modified_slots(), vec![1]);
+    assert_eq!(detect_version(save_file.as_bytes()), SaveVersion::Original);
+}
@@ -0,0 +1,34 @@
+mod common;
+
+use common::{TempDir, new_save_image};
+use pw2repac_save::{SaveFile, save_data_info::SaveDataVar};
+
+#[test]
+fn writes_replace_the_whole_file() {
+    let folder = TempDir::new("write");
+    let path = folder.join("DAT00000.dat");
+    std::fs::write(&path, new_save_image()).unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o640)).unwrap();
+    }
+
+    let mut save_file = SaveFile::from_path(&path).unwrap();
+    save_file
+        .slot_mut(1)
+        .set_int(SaveDataVar::Lives, 9)
+        .unwrap();
+    save_file.write().unwrap();
+
+    assert!(std::fs::read(&path).unwrap() == save_file.as_bytes());
+    assert!(save_file.modified_slots().is_empty());
+    // only the save is left in the folder, the temporary file was renamed over it
+    assert_eq!(std::fs::read_dir(folder.path()).unwrap().count(), 1);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o640);
+    }
+}